@@ -1,13 +1,80 @@
-use clap::Parser;
+use clap::{builder::RangedU64ValueParser, Parser, ValueEnum};
+
+/// How flushes are compared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum FlushRankingArg {
+    /// By the highest card.
+    TopCard,
+    /// By suit, then by the highest card.
+    SuitFirst,
+    /// By the rank of each card from the highest down, then by the suit.
+    CardByCard,
+}
+
+/// Who controls a seat.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SeatArg {
+    /// A person at the terminal.
+    Human,
+    /// The built-in computer player.
+    Computer,
+    /// A computer player that picks any legal move at random.
+    Random,
+}
 
 /// Command-line Big 2 card game.
 #[derive(Debug, Parser)]
 #[clap(author, version, about, long_about = None)]
 pub struct GameArgs {
-    /// Number of players.
-    #[clap(short, long, default_value_t = 4)]
+    /// Number of players, from 2 to 8.
+    #[clap(short, long, default_value_t = 4, value_parser = RangedU64ValueParser::<usize>::new().range(2..=8))]
     pub players: usize,
+    /// Number of decks. Defaults to two decks for more than four players, which need at least two.
+    #[clap(long, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    pub decks: Option<usize>,
+    /// Set aside cards that can't be dealt evenly instead of giving them to the starting player.
+    #[clap(long)]
+    pub discard_leftover: bool,
+    /// Play deals until a player reaches this penalty score. Defaults to a single deal.
+    #[clap(long)]
+    pub target_score: Option<usize>,
+    /// Let the winner of the previous deal lead the next deal with any hand.
+    #[clap(long)]
+    pub winner_leads: bool,
+    /// Play Tiến lên. Sequences of any length replace five-card combos and 2s can be chopped.
+    #[clap(long)]
+    pub tien_len: bool,
+    /// Allow three cards of the same rank to be played as a hand.
+    #[clap(long, conflicts_with = "tien-len")]
+    pub triples: bool,
+    /// Allow four cards of the same rank to be played as a hand.
+    #[clap(long, conflicts_with = "tien-len")]
+    pub quads: bool,
+    /// Let four of a kinds and straight flushes be played on top of any trick.
+    #[clap(long, conflicts_with = "tien-len")]
+    pub bomb_interrupts: bool,
+    /// How flushes are compared.
+    #[clap(long, value_enum, default_value_t = FlushRankingArg::TopCard, conflicts_with = "tien-len")]
+    pub flush_ranking: FlushRankingArg,
+    /// Make the player before someone with one card left lead their highest single.
+    #[clap(long)]
+    pub last_card: bool,
+    /// Win the deal instantly with a dragon (3 to 2), four 2s or six pairs.
+    #[clap(long)]
+    pub instant_wins: bool,
+    /// Keep playing after the first player goes out to rank every player.
+    #[clap(long)]
+    pub play_out: bool,
+    /// Seed used to shuffle the first deal. The same seed and moves replay the same game.
+    #[clap(long)]
+    pub seed: Option<u64>,
+    /// Disable undo and redo, as in rated games.
+    #[clap(long)]
+    pub rated: bool,
     /// Play a hotseat game without AI.
     #[clap(long)]
     pub hotseat: bool,
+    /// Who controls each seat in order, e.g. human,computer,human,random. Unlisted seats are computers
+    #[clap(long, value_enum, use_value_delimiter = true)]
+    pub seats: Vec<SeatArg>,
 }
@@ -1,4 +1,4 @@
-use crate::common::{rank::Rank, suit::Suit};
+use crate::common::{rank::Rank, rules::RuleSet, suit::Suit};
 
 use serde::{Deserialize, Serialize};
 use std::{
@@ -6,30 +6,65 @@ use std::{
     fmt::{self, Debug},
 };
 
+/// A single playing card.
+///
+/// Games with more than one deck can have cards with the same rank and suit.
+/// These are told apart by the `deck` they came from.
+/// - Identical cards have the same `value()` so neither beats the other.
+/// - When sorting, the card from the lower `deck` is ordered first.
+///
+/// `Ord` follows the standard `RuleSet`. Use `RuleSet::cmp_cards` to follow a configured suit order.
 #[derive(Serialize, Deserialize, Eq, PartialEq, Clone, Copy, Hash)]
 pub struct Card {
     pub rank: Rank,
     pub suit: Suit,
+    /// Index of the deck the card came from.
+    #[serde(default)]
+    pub deck: usize,
 }
 
 impl Card {
-    /// Assign value of card based on rank and suit.
-    pub fn value(&self) -> f32 {
-        // rank (ace: 12) + ((spade: 4) / 10.0) -> 12.4
-        (self.rank as usize) as f32 + ((self.suit as usize) as f32 / 10.0)
+    /// Create a card from the first deck.
+    pub const fn new(rank: Rank, suit: Suit) -> Card {
+        Card {
+            rank,
+            suit,
+            deck: 0,
+        }
+    }
+
+    /// Check if two cards have the same rank and suit, regardless of deck.
+    pub fn is_same_face(&self, other: &Card) -> bool {
+        self.rank == other.rank && self.suit == other.suit
+    }
+
+    /// Assign value of card based on rank and suit using the standard `RuleSet`.
+    pub fn value(&self) -> usize {
+        RuleSet::default().card_value(self)
     }
 }
 
 impl Debug for Card {
+    /// Cards from other decks than the first are marked with their deck number, e.g. `[♦9:2]`.
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "[{}{}]", self.suit.as_str(), self.rank.as_str())
+        if self.deck == 0 {
+            write!(f, "[{}{}]", self.suit.as_str(), self.rank.as_str())
+        } else {
+            write!(
+                f,
+                "[{}{}:{}]",
+                self.suit.as_str(),
+                self.rank.as_str(),
+                self.deck + 1
+            )
+        }
     }
 }
 
 impl Ord for Card {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        // Compare cards based on value.
-        self.value().partial_cmp(&other.value()).unwrap()
+        // Compare cards based on the standard rules.
+        RuleSet::default().cmp_cards(self, other)
     }
 
     fn max(self, other: Self) -> Self
@@ -83,13 +118,27 @@ mod tests {
         let card_1 = Card {
             rank: Rank::Ace,
             suit: Suit::Club,
+            deck: 0,
         };
 
         let card_2 = Card {
             rank: Rank::Ace,
             suit: Suit::Spade,
+            deck: 0,
         };
 
         assert!(card_1.lt(&card_2))
     }
+
+    #[test]
+    fn test_identical_card_cmp() {
+        let card_1 = Card::new(Rank::Ace, Suit::Club);
+        let card_2 = Card { deck: 1, ..card_1 };
+
+        assert_ne!(card_1, card_2);
+        assert!(card_1.is_same_face(&card_2));
+        assert_eq!(card_1.value(), card_2.value());
+        assert!(card_1 < card_2);
+        assert_eq!(format!("{card_1:?} {card_2:?}"), "[♣A] [♣A:2]");
+    }
 }
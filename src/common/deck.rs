@@ -1,50 +1,176 @@
 use crate::common::{card::Card, error::DeckError, rank::Rank, suit::Suit};
 use itertools::Itertools;
 use rand::seq::SliceRandom;
-use rand::thread_rng;
+use rand::{thread_rng, Rng};
 use strum::IntoEnumIterator;
 
+/// Cards in a standard deck.
+pub const DECK_SIZE: usize = 52;
+
+/// One or more standard 52 card decks.
 #[derive(Debug, Clone)]
 pub struct Deck {
     pub cards: Vec<Card>,
 }
 
 impl Deck {
-    pub fn new(shuffle: bool) -> Result<Deck, ()> {
-        let mut cards: Vec<Card> = Vec::with_capacity(52);
+    /// Create a new deck of cards, optionally shuffling it.
+    pub fn new(shuffle: bool) -> Result<Deck, DeckError> {
+        Deck::with_decks(1, shuffle)
+    }
+
+    /// Create `n_decks` standard decks combined together, optionally shuffling them.
+    pub fn with_decks(n_decks: usize, shuffle: bool) -> Result<Deck, DeckError> {
+        if n_decks == 0 {
+            return Err(DeckError::NoDecks(
+                "Deck must have at least one standard deck.".to_string(),
+            ));
+        }
+        let mut cards: Vec<Card> = Vec::with_capacity(DECK_SIZE * n_decks);
 
-        for suit in Suit::iter() {
-            for rank in Rank::iter() {
-                let new_card = Card { suit, rank };
-                cards.push(new_card);
+        for deck in 0..n_decks {
+            for suit in Suit::iter() {
+                for rank in Rank::iter() {
+                    let new_card = Card { suit, rank, deck };
+                    cards.push(new_card);
+                }
             }
         }
 
+        let mut deck = Deck { cards };
         if shuffle {
-            let mut rng = thread_rng();
-            cards.shuffle(&mut rng);
+            deck.shuffle(&mut thread_rng());
         }
 
-        Ok(Deck { cards })
+        Ok(deck)
     }
 
+    /// Shuffle the cards using the `rng`.
+    ///
+    /// The same seeded `rng` always gives the same order.
+    pub fn shuffle<R: Rng + ?Sized>(&mut self, rng: &mut R) -> &Deck {
+        self.cards.shuffle(rng);
+        self
+    }
+
+    /// Divide the deck into `n_chunks` of equal size.
+    ///
+    /// Cards that cannot be divided evenly are left out. Use [`Deck::deal`] to get them.
     pub fn divide(&self, n_chunks: usize) -> Result<Vec<Vec<&Card>>, DeckError> {
-        // TODO: Implement odd n-players and 3 of diamonds rule.
-        if n_chunks > 52 {
-            let err_msg = format!("Deck cannot have greater than 52 chunks. ({}) ", n_chunks);
-            return Err(DeckError::InvalidChunks(err_msg));
+        if n_chunks == 0 {
+            return Err(DeckError::InvalidChunks(
+                "Deck cannot be divided into 0 chunks.".to_string(),
+            ));
         }
+        let (player_cards, _) = self.deal(n_chunks, self.cards.len() / n_chunks)?;
+        Ok(player_cards)
+    }
 
-        let player_card_cnt = self.cards.len() / n_chunks;
+    /// Deal `n_chunks` of `chunk_size` cards.
+    ///
+    /// Returns the dealt chunks and the cards left over.
+    pub fn deal(
+        &self,
+        n_chunks: usize,
+        chunk_size: usize,
+    ) -> Result<(Vec<Vec<&Card>>, Vec<&Card>), DeckError> {
+        if n_chunks > self.cards.len() {
+            let err_msg = format!(
+                "Deck cannot have greater than {} chunks. ({}) ",
+                self.cards.len(),
+                n_chunks
+            );
+            return Err(DeckError::InvalidChunks(err_msg));
+        }
+        if n_chunks * chunk_size > self.cards.len() || chunk_size == 0 {
+            let err_msg = format!(
+                "Deck of {} cards cannot be dealt into {} chunks of {} cards.",
+                self.cards.len(),
+                n_chunks,
+                chunk_size
+            );
+            return Err(DeckError::InvalidChunks(err_msg));
+        }
 
-        let card_chunks = self.cards.iter().chunks(player_card_cnt);
+        let n_dealt = n_chunks * chunk_size;
+        let card_chunks = self.cards[..n_dealt].iter().chunks(chunk_size);
 
         let mut player_cards: Vec<Vec<&Card>> = vec![];
         for cards in &card_chunks {
             let player_n_cards = cards.collect::<Vec<&Card>>();
             player_cards.push(player_n_cards);
         }
+        let leftover_cards = self.cards[n_dealt..].iter().collect_vec();
 
-        Ok(player_cards)
+        Ok((player_cards, leftover_cards))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Deck;
+    use crate::common::error::DeckError;
+    use itertools::Itertools;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn test_divide() {
+        let deck = Deck::new(true).unwrap();
+
+        for n_chunks in 1..=8 {
+            let chunks = deck.divide(n_chunks).unwrap();
+            assert_eq!(chunks.len(), n_chunks);
+            assert!(chunks.iter().all(|chunk| chunk.len() == 52 / n_chunks));
+        }
+        assert!(deck.divide(0).is_err());
+        assert!(deck.divide(53).is_err());
+    }
+
+    #[test]
+    fn test_shuffle() {
+        let mut deck = Deck::new(false).unwrap();
+        let mut other_deck = deck.clone();
+        deck.shuffle(&mut ChaCha8Rng::seed_from_u64(7));
+        other_deck.shuffle(&mut ChaCha8Rng::seed_from_u64(7));
+
+        assert_eq!(deck.cards, other_deck.cards);
+        assert_ne!(deck.cards, Deck::new(false).unwrap().cards);
+    }
+
+    #[test]
+    fn test_deal() {
+        let deck = Deck::new(true).unwrap();
+
+        let (chunks, leftover) = deck.deal(3, 17).unwrap();
+        assert_eq!(chunks.len(), 3);
+        assert!(chunks.iter().all(|chunk| chunk.len() == 17));
+        assert_eq!(leftover, vec![&deck.cards[51]]);
+
+        assert!(deck.deal(4, 14).is_err());
+        assert!(deck.deal(4, 0).is_err());
+    }
+
+    #[test]
+    fn test_multiple_decks() {
+        let deck = Deck::with_decks(2, true).unwrap();
+        assert_eq!(deck.cards.len(), 104);
+        assert_eq!(deck.cards.iter().unique().count(), 104);
+        assert_eq!(
+            deck.cards
+                .iter()
+                .filter(|card| card.is_same_face(&deck.cards[0]))
+                .count(),
+            2
+        );
+
+        let (chunks, leftover) = deck.deal(8, 13).unwrap();
+        assert_eq!(chunks.len(), 8);
+        assert!(leftover.is_empty());
+
+        assert!(matches!(
+            Deck::with_decks(0, false),
+            Err(DeckError::NoDecks(_))
+        ));
     }
 }
@@ -1,16 +1,22 @@
-use std::io::Error;
+use std::fmt;
 
+/// Deck could not be divided.
 #[derive(Debug)]
 pub struct InvalidChunks;
 
+/// Hands could not be compared.
 #[derive(Debug)]
 pub struct InvalidComparison;
 
+/// Errors from creating or dividing a `Deck`.
 #[derive(Debug)]
 pub enum DeckError {
     InvalidChunks(String),
+    /// A deck was requested with no standard decks in it.
+    NoDecks(String),
 }
 
+/// Errors from validating or comparing a `Hand`.
 #[derive(Debug)]
 //https://web.mit.edu/rust-lang_v1.25/arch/amd64_ubuntu1404/share/doc/rust/html/book/first-edition/README.html
 pub enum HandError {
@@ -18,3 +24,29 @@ pub enum HandError {
     InvalidChunks(String),
     InvalidComparison(String),
 }
+
+/// Errors from creating a `Game` or applying an `Action` to it.
+#[derive(Debug)]
+pub enum GameError {
+    InvalidDeal(String),
+    InvalidMove(String),
+    /// A card was played more than once.
+    DuplicateCard(String),
+    /// A card played is not held by the player.
+    CardNotHeld(String),
+    GameOver(String),
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameError::InvalidDeal(msg)
+            | GameError::InvalidMove(msg)
+            | GameError::DuplicateCard(msg)
+            | GameError::CardNotHeld(msg)
+            | GameError::GameOver(msg) => {
+                write!(f, "{msg}")
+            }
+        }
+    }
+}
@@ -1,15 +1,28 @@
-use crate::common::{card::Card, error::HandError, player::Player, rank::Rank, suit::Suit};
+use crate::common::{
+    card::Card,
+    error::HandError,
+    player::Player,
+    rank::Rank,
+    rules::{FlushRanking, RuleSet, StraightRules, Variant},
+    suit::Suit,
+};
 use itertools::Itertools;
-use std::cmp::Ordering::{Equal, Greater, Less};
+use std::cmp::Ordering;
 use std::collections::{hash_map::Entry::Vacant, HashMap};
-use std::f32;
 
+/// Kinds of hands. Discriminant is the number of cards in fixed-size hands.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum HandType {
     None = 0,
     Single = 1,
     Double = 2,
+    Triple = 3,
+    Quad = 4,
     Combo = 5,
+    /// Three or more cards of consecutive ranks. Tiến lên only.
+    Sequence,
+    /// Three or more pairs of consecutive ranks. Tiến lên only.
+    DoubleSequence,
 }
 
 /// Combo types reference: https://www.pagat.com/climbing/bigtwo.html
@@ -45,35 +58,72 @@ pub struct Hand {
     pub player: usize,
 }
 
+/// Measure the strength of a hand.
+///
+/// Strength is an exact ranking key. Hands of the same `HandType` compare by their keys.
+/// - Bits 48..: Hand category. The `ComboType` for combos.
+/// - Bits 0..48: Values that decide the hand, 8 bits each, most significant first.
+///   Card values, preceded by the position of the straight for straights.
+///   Flushes depend on the `FlushRanking`.
 pub trait Gauge {
     const STRONGEST_FILTER: [CardFilter; 1];
     const FREQ_STRONGEST_FILTER: [CardFilter; 2];
     fn invalid_hand_err_msg(&self) -> String;
     fn empty_combos_err_msg(&self, filters: &[CardFilter]) -> String;
-    /// Calculates the strength of a `Hand`.
-    fn strength(&self) -> Result<f32, HandError>;
+    /// Calculates the strength of a `Hand` using the standard `RuleSet`.
+    fn strength(&self) -> Result<u64, HandError>;
+    /// Calculates the strength of a `Hand` using the given `rules`.
+    fn strength_with(&self, rules: &RuleSet) -> Result<u64, HandError>;
 }
 
+/// Count and filter the cards of a hand.
 pub trait Parse {
     /// Retrives a set of `Card`s from `Hand` based on a series of `CardFilter` conditions.
     fn get_cards(&self, filters: &[CardFilter]) -> Option<Vec<Card>>;
+    /// Same as `get_cards`, comparing cards using the given `rules`.
+    fn get_cards_with(&self, filters: &[CardFilter], rules: &RuleSet) -> Option<Vec<Card>>;
     fn ranks(&self, opt_cards: Option<&[Card]>) -> HashMap<Rank, usize>;
     fn suits(&self, opt_cards: Option<&[Card]>) -> HashMap<Suit, usize>;
 }
 
+/// Classify a set of cards into a `HandType` and `ComboType`.
 pub trait Validate {
-    fn is_valid(hand: &[Card]) -> Result<(HandType, ComboType), &'static str>;
-    fn is_combo_type(hand: &[Card]) -> ComboType;
+    fn is_valid(
+        hand: &[Card],
+        straights: &StraightRules,
+    ) -> Result<(HandType, ComboType), &'static str>;
+    fn is_combo_type(hand: &[Card], straights: &StraightRules) -> ComboType;
     fn is_flush(hand: &[Card]) -> bool;
-    fn is_straight(hand: &[Card]) -> bool;
+    fn is_straight(hand: &[Card], straights: &StraightRules) -> bool;
     fn is_dupe_combo(hand: &[Card], combo_type: ComboType) -> bool;
-    fn is_royal_flush(hand: &[Card]) -> bool;
+    fn is_royal_flush(hand: &[Card], straights: &StraightRules) -> bool;
+    fn is_tien_len_valid(hand: &[Card]) -> Result<(HandType, ComboType), &'static str>;
+    fn is_sequence(hand: &[Card]) -> bool;
+    fn is_double_sequence(hand: &[Card]) -> bool;
 }
 
+/// Minimum number of ranks in a sequence or double sequence.
+pub const MIN_SEQUENCE_LEN: usize = 3;
+
 impl Hand {
-    /// Create a new hand and evaluates if it is valid or not.
+    /// Create a new hand and evaluates if it is valid or not using the standard `RuleSet`.
     pub fn new(hand: &[Card], player: &Player) -> Result<Hand, &'static str> {
-        let valid_hand = Hand::is_valid(hand);
+        Hand::with_rules(hand, player, &RuleSet::default())
+    }
+
+    /// Create a new hand and evaluates if it is valid or not under the `rules` variant.
+    pub fn with_rules(
+        hand: &[Card],
+        player: &Player,
+        rules: &RuleSet,
+    ) -> Result<Hand, &'static str> {
+        if !hand.iter().all_unique() {
+            return Err("Hand contains the same card more than once.");
+        }
+        let valid_hand = match rules.variant {
+            Variant::BigTwo => Hand::is_valid(hand, &rules.straights),
+            Variant::TienLen => Hand::is_tien_len_valid(hand),
+        };
         if let Ok((hand_type, combo_type)) = valid_hand {
             let new_hand = Hand {
                 cards: hand.to_vec(),
@@ -91,6 +141,10 @@ impl Hand {
 // TODO: Implement trait for deck.
 impl Parse for Hand {
     fn get_cards(&self, filters: &[CardFilter]) -> Option<Vec<Card>> {
+        self.get_cards_with(filters, &RuleSet::default())
+    }
+
+    fn get_cards_with(&self, filters: &[CardFilter], rules: &RuleSet) -> Option<Vec<Card>> {
         let mut filtered_cards = Some(self.cards.clone());
 
         for filter_opt in filters.iter() {
@@ -101,11 +155,11 @@ impl Parse for Hand {
             filtered_cards = match filter_opt {
                 CardFilter::Strongest => filtered_cards?
                     .into_iter()
-                    .reduce(|c1, c2| c1.max(c2))
+                    .max_by(|c1, c2| rules.cmp_cards(c1, c2))
                     .map(|strongest_card| vec![strongest_card]),
                 CardFilter::Weakest => filtered_cards?
                     .into_iter()
-                    .reduce(|c1, c2| c1.min(c2))
+                    .min_by(|c1, c2| rules.cmp_cards(c1, c2))
                     .map(|weakest_card| vec![weakest_card]),
                 // TODO: Simplify and separate repeat code.
                 CardFilter::MostFrequentSuits => {
@@ -226,156 +280,197 @@ impl Gauge for Hand {
 
     const STRONGEST_FILTER: [CardFilter; 1] = [CardFilter::Strongest];
 
-    fn strength(&self) -> Result<f32, HandError> {
-        match self.kind {
-            HandType::Single => {
-                // Return card's base value.
-                self.cards.first().map_or(
-                    Err(HandError::InvalidHand(self.invalid_hand_err_msg())),
-                    |card| Ok(card.value()),
-                )
+    fn strength(&self) -> Result<u64, HandError> {
+        self.strength_with(&RuleSet::default())
+    }
+
+    fn strength_with(&self, rules: &RuleSet) -> Result<u64, HandError> {
+        let strongest_card = |cards: Vec<Card>| {
+            cards
+                .into_iter()
+                .max_by(|card_1, card_2| rules.cmp_cards(card_1, card_2))
+        };
+
+        // Straights are decided by their position and then the top card.
+        let straight_values = || {
+            let (position, top_rank) = rules.straights.rank_straight(&self.cards)?;
+            let top_cards = self
+                .cards
+                .iter()
+                .filter(|card| card.rank == top_rank)
+                .copied()
+                .collect_vec();
+            let top_card = strongest_card(top_cards)?;
+            Some(vec![position as u64, rules.card_value(&top_card) as u64])
+        };
+        let card_values =
+            |card: Option<Card>| card.map(|card| vec![rules.card_value(&card) as u64]);
+        let flush_values = || {
+            let top_card = strongest_card(self.cards.clone())?;
+            let suit_value = rules.suit_value(top_card.suit) as u64;
+            Some(match rules.flush_ranking {
+                FlushRanking::TopCard => vec![rules.card_value(&top_card) as u64],
+                FlushRanking::SuitFirst => vec![suit_value, rules.card_value(&top_card) as u64],
+                FlushRanking::CardByCard => self
+                    .cards
+                    .iter()
+                    .map(|card| card.rank as u64)
+                    .sorted()
+                    .rev()
+                    .chain([suit_value])
+                    .collect_vec(),
+            })
+        };
+
+        // Get the category and the values deciding the hand.
+        let (category, deciding_values) = match (self.kind, self.combo) {
+            (HandType::Single, _) => (0, card_values(self.cards.first().copied())),
+            (
+                HandType::Double
+                | HandType::Triple
+                | HandType::Quad
+                | HandType::Sequence
+                | HandType::DoubleSequence,
+                _,
+            ) => (0, card_values(strongest_card(self.cards.clone()))),
+            (
+                HandType::Combo,
+                ComboType::Straight | ComboType::StraightFlush | ComboType::RoyalFlush,
+            ) => (self.combo as u64, straight_values()),
+            (HandType::Combo, ComboType::Flush) => (self.combo as u64, flush_values()),
+            (HandType::Combo, ComboType::FullHouse | ComboType::Bomb) => (
+                self.combo as u64,
+                // Compare by the strongest card of the triple or quad.
+                card_values(
+                    self.get_cards_with(&[CardFilter::MostFrequentRanks], rules)
+                        .and_then(strongest_card),
+                ),
+            ),
+            (HandType::Combo, _) => {
+                return Err(HandError::InvalidHand(
+                    "Error: Invalid combo type.".to_string(),
+                ))
             }
-            HandType::Double => {
-                if let Some(strongest_card) = self.get_cards(&Hand::STRONGEST_FILTER) {
-                    // Multiply by 2.0 for doubles.
-                    strongest_card.last().map_or(
-                        Err(HandError::InvalidHand(self.invalid_hand_err_msg())),
-                        |last_card| Ok(last_card.value() * 2.0),
-                    )
-                } else {
-                    Err(HandError::InvalidHand(
-                        self.empty_combos_err_msg(&Hand::STRONGEST_FILTER),
-                    ))
-                }
+            (HandType::None, _) => {
+                return Err(HandError::InvalidHand(
+                    "Error: Cannot calculate hand strength for invalid/empty hand.".to_string(),
+                ))
             }
-            HandType::Combo => {
-                // Lowest card value is 1.1 (3 of Diamonds), highest is 13.4 (2 of Spades)
-                let hand_strength_multiplier_res: Result<f32, HandError> = match self.combo {
-                    ComboType::Straight
-                    | ComboType::Flush
-                    | ComboType::StraightFlush
-                    | ComboType::RoyalFlush => {
-                        if let Some(strongest_card) = self.get_cards(&Hand::STRONGEST_FILTER) {
-                            let combo_multiplier = (self.combo as usize) as f32;
-                            strongest_card
-                                .last()
-                                .map_or(Err(HandError::InvalidHand("".to_string())), |card| {
-                                    Ok(card.value().powf(combo_multiplier))
-                                })
-                        } else {
-                            Err(HandError::InvalidHand(
-                                self.empty_combos_err_msg(&Hand::STRONGEST_FILTER),
-                            ))
-                        }
-                    }
-                    ComboType::FullHouse | ComboType::Bomb => {
-                        if let Some(dupes) = self.get_cards(&Hand::FREQ_STRONGEST_FILTER) {
-                            let combo_multiplier = (self.combo as usize) as f32;
-                            dupes.iter().max().map_or(
-                                Err(HandError::InvalidHand(
-                                    self.empty_combos_err_msg(&Hand::FREQ_STRONGEST_FILTER),
-                                )),
-                                // Multiply by 4.0 to ensure that flushes are weaker than full-houses.
-                                |strongest_card| {
-                                    Ok(strongest_card.value().powf(combo_multiplier))
-                                },
-                            )
-                        } else {
-                            Err(HandError::InvalidHand(
-                                self.empty_combos_err_msg(&Hand::FREQ_STRONGEST_FILTER),
-                            ))
-                        }
-                    }
-                    _ => Err(HandError::InvalidHand(
-                        "Error: Invalid combo type.".to_string(),
-                    )),
-                };
+        };
 
-                if let Ok(hand_strength_multiplier) = hand_strength_multiplier_res {
-                    // Multiply by 5.0 for combo and raise combo to power of multiplier.
-                    return Ok(hand_strength_multiplier * 5.0);
-                } else {
-                    return Err(hand_strength_multiplier_res.unwrap_err());
-                }
-            }
-            HandType::None => Err(HandError::InvalidHand(
-                "Error: Cannot calculate hand strength for invalid/empty hand.".to_string(),
-            )),
-        }
+        let Some(deciding_values) = deciding_values else {
+            return Err(HandError::InvalidHand(self.invalid_hand_err_msg()));
+        };
+
+        let values = deciding_values
+            .iter()
+            .take(6)
+            .fold(0, |key, value| (key << 8) | value);
+        Ok((category << 48) | values)
     }
 }
 
-impl Ord for Hand {
-    fn max(self, other: Self) -> Self
-    where
-        Self: Sized,
-    {
-        std::cmp::max_by(self, other, Ord::cmp)
+impl Hand {
+    /// Check if the hand is a bomb. Four of a kind or a straight flush.
+    pub fn is_bomb(&self) -> bool {
+        self.kind == HandType::Quad
+            || matches!(
+                self.combo,
+                ComboType::Bomb | ComboType::StraightFlush | ComboType::RoyalFlush
+            )
     }
 
-    fn min(self, other: Self) -> Self
-    where
-        Self: Sized,
-    {
-        std::cmp::min_by(self, other, Ord::cmp)
+    /// Check if the hand chops `other` in Tiến lên.
+    ///
+    /// - Three pairs in sequence or four of a kind chop a single 2.
+    /// - Four of a kind also chops three pairs in sequence.
+    /// - Four or more pairs in sequence chop a single 2, a pair of 2s, four of a kind and shorter double sequences.
+    pub fn chops(&self, other: &Hand) -> bool {
+        let is_twos = other.cards.iter().all(|card| card.rank == Rank::Two);
+        let n_pairs = |hand: &Hand| {
+            if hand.kind == HandType::DoubleSequence {
+                hand.cards.len() / 2
+            } else {
+                0
+            }
+        };
+
+        match (self.kind, other.kind) {
+            (HandType::DoubleSequence, HandType::Single) => is_twos,
+            (HandType::Quad, HandType::Single) => is_twos,
+            (HandType::Quad, HandType::DoubleSequence) => n_pairs(other) == MIN_SEQUENCE_LEN,
+            (HandType::DoubleSequence, HandType::Double) => is_twos && n_pairs(self) >= 4,
+            (HandType::DoubleSequence, HandType::Quad) => n_pairs(self) >= 4,
+            (HandType::DoubleSequence, HandType::DoubleSequence) => {
+                n_pairs(self) >= 4 && n_pairs(self) > n_pairs(other)
+            }
+            _ => false,
+        }
     }
 
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        // Compare hands based on value.
-        if self.kind != other.kind {
-            panic!("Error: Unable to compare hands of different kind.")
+    /// Strength of a bomb using the given `rules`. Four of a kind without a kicker ranks as a `ComboType::Bomb`.
+    ///
+    /// Returns `None` if the hand isn't a bomb.
+    pub fn bomb_strength_with(&self, rules: &RuleSet) -> Option<u64> {
+        if !self.is_bomb() {
+            return None;
         }
-        if let (Ok(hand_1_strength), Ok(hand_2_strength)) = (self.strength(), other.strength()) {
-            hand_1_strength.partial_cmp(&hand_2_strength).unwrap()
+        let strength = self.strength_with(rules).ok()?;
+        if self.kind == HandType::Quad {
+            Some(((ComboType::Bomb as u64) << 48) | strength)
         } else {
-            panic!("Error: Unable to calculate hand strengths.")
+            Some(strength)
         }
     }
-}
 
-impl PartialOrd for Hand {
-    fn lt(&self, other: &Self) -> bool {
-        matches!(self.partial_cmp(other), Some(Less))
-    }
-
-    fn le(&self, other: &Self) -> bool {
-        // Pattern `Some(Less | Eq)` optimizes worse than negating `None | Some(Greater)`.
-        // FIXME: The root cause was fixed upstream in LLVM with:
-        // https://github.com/llvm/llvm-project/commit/9bad7de9a3fb844f1ca2965f35d0c2a3d1e11775
-        // Revert this workaround once support for LLVM 12 gets dropped.
-        !matches!(self.partial_cmp(other), None | Some(Greater))
-    }
-
-    fn gt(&self, other: &Self) -> bool {
-        matches!(self.partial_cmp(other), Some(Greater))
-    }
-
-    fn ge(&self, other: &Self) -> bool {
-        matches!(self.partial_cmp(other), Some(Greater | Equal))
-    }
-
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
+    /// Compare hands using the given `rules`.
+    ///
+    /// Returns `None` if the hands are of different kinds or lengths or either hand is invalid.
+    pub fn partial_cmp_with(&self, other: &Hand, rules: &RuleSet) -> Option<Ordering> {
+        if self.kind != other.kind || self.cards.len() != other.cards.len() {
+            return None;
+        }
+        let hand_1_strength = self.strength_with(rules).ok()?;
+        let hand_2_strength = other.strength_with(rules).ok()?;
+        Some(hand_1_strength.cmp(&hand_2_strength))
     }
 }
 
 impl Validate for Hand {
     /// Check if a hand is valid based on it's length and if it is a combo, where it is valid or not.
-    fn is_valid(hand: &[Card]) -> Result<(HandType, ComboType), &'static str> {
+    fn is_valid(
+        hand: &[Card],
+        straights: &StraightRules,
+    ) -> Result<(HandType, ComboType), &'static str> {
+        let is_same_rank = hand
+            .iter()
+            .all(|card| hand.first().unwrap().rank == card.rank);
+
         match hand.len() {
             1 => Ok((HandType::Single, ComboType::None)),
             2 => {
-                let is_double = hand
-                    .iter()
-                    .all(|card| hand.get(0).unwrap().rank == card.rank);
-                if is_double {
+                if is_same_rank {
                     Ok((HandType::Double, ComboType::None))
                 } else {
                     Err("Error: Not all cards in double are equal rank.")
                 }
             }
+            3 => {
+                if is_same_rank {
+                    Ok((HandType::Triple, ComboType::None))
+                } else {
+                    Err("Error: Not all cards in triple are equal rank.")
+                }
+            }
+            4 => {
+                if is_same_rank {
+                    Ok((HandType::Quad, ComboType::None))
+                } else {
+                    Err("Error: Not all cards in quad are equal rank.")
+                }
+            }
             5 => {
-                let combo_type = Hand::is_combo_type(hand);
+                let combo_type = Hand::is_combo_type(hand, straights);
 
                 // If combo type isn't None.
                 if combo_type.ne(&ComboType::None) {
@@ -388,12 +483,12 @@ impl Validate for Hand {
         }
     }
 
-    fn is_combo_type(hand: &[Card]) -> ComboType {
+    fn is_combo_type(hand: &[Card], straights: &StraightRules) -> ComboType {
         let is_flush = Hand::is_flush(hand);
-        let is_straight = Hand::is_straight(hand);
+        let is_straight = Hand::is_straight(hand, straights);
         let is_bomb = Hand::is_dupe_combo(hand, ComboType::Bomb);
         let is_full_house = Hand::is_dupe_combo(hand, ComboType::FullHouse);
-        let is_royal_flush = Hand::is_royal_flush(hand);
+        let is_royal_flush = Hand::is_royal_flush(hand, straights);
         let is_straight_flush = is_flush && is_straight;
 
         if is_royal_flush {
@@ -417,18 +512,11 @@ impl Validate for Hand {
         hand.len() == HandType::Combo as usize
             && hand
                 .iter()
-                .all(|card| card.suit == hand.get(0).unwrap().suit)
+                .all(|card| card.suit == hand.first().unwrap().suit)
     }
 
-    fn is_straight(hand: &[Card]) -> bool {
-        hand.len() == HandType::Combo as usize
-            && hand
-                .iter()
-                .sorted()
-                .tuple_windows()
-                .all(|card_pair: (&Card, &Card)| {
-                    (card_pair.1.rank as usize - card_pair.0.rank as usize) == 1
-                })
+    fn is_straight(hand: &[Card], straights: &StraightRules) -> bool {
+        straights.rank_straight(hand).is_some()
     }
 
     fn is_dupe_combo(hand: &[Card], combo_type: ComboType) -> bool {
@@ -457,13 +545,61 @@ impl Validate for Hand {
         }
     }
 
-    fn is_royal_flush(hand: &[Card]) -> bool {
-        if Hand::is_flush(hand) {
-            let royal_flush_ranks: [usize; 5] = [8, 9, 10, 11, 12];
-            hand.iter()
-                .sorted()
+    /// Check if a hand is valid in Tiến lên. There are no five-card combos.
+    fn is_tien_len_valid(hand: &[Card]) -> Result<(HandType, ComboType), &'static str> {
+        let is_same_rank = hand
+            .iter()
+            .all(|card| hand.first().unwrap().rank == card.rank);
+
+        match hand.len() {
+            0 => Err("Error: Hand has invalid length."),
+            1 => Ok((HandType::Single, ComboType::None)),
+            2 if is_same_rank => Ok((HandType::Double, ComboType::None)),
+            3 if is_same_rank => Ok((HandType::Triple, ComboType::None)),
+            4 if is_same_rank => Ok((HandType::Quad, ComboType::None)),
+            _ if Hand::is_sequence(hand) => Ok((HandType::Sequence, ComboType::None)),
+            _ if Hand::is_double_sequence(hand) => Ok((HandType::DoubleSequence, ComboType::None)),
+            _ => Err("Error: Hand is not a valid set or sequence."),
+        }
+    }
+
+    fn is_sequence(hand: &[Card]) -> bool {
+        // 2s can't be part of a sequence.
+        hand.len() >= MIN_SEQUENCE_LEN
+            && hand.iter().all(|card| card.rank != Rank::Two)
+            && hand
+                .iter()
                 .map(|card| card.rank as usize)
-                .eq(royal_flush_ranks)
+                .sorted()
+                .tuple_windows()
+                .all(|(rank_1, rank_2)| rank_2 - rank_1 == 1)
+    }
+
+    fn is_double_sequence(hand: &[Card]) -> bool {
+        let ranks = hand
+            .iter()
+            .map(|card| card.rank as usize)
+            .sorted()
+            .collect_vec();
+        let pair_ranks = ranks.iter().step_by(2).copied().collect_vec();
+
+        hand.len() >= MIN_SEQUENCE_LEN * 2
+            && hand.len().is_multiple_of(2)
+            && hand.iter().all(|card| card.rank != Rank::Two)
+            // Each rank is paired.
+            && ranks.chunks(2).all(|pair| pair[0] == pair[1])
+            && pair_ranks
+                .iter()
+                .tuple_windows()
+                .all(|(rank_1, rank_2)| rank_2 - rank_1 == 1)
+    }
+
+    fn is_royal_flush(hand: &[Card], straights: &StraightRules) -> bool {
+        if Hand::is_flush(hand) && Hand::is_straight(hand, straights) {
+            hand.iter()
+                .map(|card| card.rank)
+                .sorted_by_key(|rank| *rank as usize)
+                .eq(straights.royal_ranks())
         } else {
             false
         }
@@ -472,9 +608,16 @@ impl Validate for Hand {
 
 #[cfg(test)]
 pub mod tests {
-    use super::{ComboType, Gauge, Hand, Parse, Validate};
-    use crate::common::{card::Card, player::Player, rank::Rank, suit::Suit};
+    use super::{CardFilter, ComboType, Gauge, Hand, HandType, Parse, Validate};
+    use crate::common::{
+        card::Card,
+        player::Player,
+        rank::Rank,
+        rules::{FlushRanking, RuleSet, StraightRanking, StraightRules},
+        suit::Suit,
+    };
     use serde_json::from_reader;
+    use std::cmp::Ordering::{Equal, Greater, Less};
     use std::fs::File;
 
     #[derive(Debug, PartialEq, Eq)]
@@ -520,7 +663,7 @@ pub mod tests {
         };
 
         if let Some(cards) = read_cards {
-            let hand_res = Hand::new(&cards, &test_player);
+            let hand_res = Hand::new(&cards, test_player);
             if let Ok(hand) = hand_res {
                 Ok(hand)
             } else {
@@ -555,6 +698,191 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn test_create_hand_duplicate_cards() {
+        let card = Card::new(Rank::Three, Suit::Diamond);
+        let test_player = Player {
+            id: 1,
+            cards: vec![card],
+        };
+        assert!(Hand::new(&[card, card], &test_player).is_err());
+
+        // Copies from different decks are different cards.
+        let other_deck_card = Card { deck: 1, ..card };
+        let hand = Hand::new(&[card, other_deck_card], &test_player).unwrap();
+        assert_eq!(hand.kind, HandType::Double);
+    }
+
+    #[test]
+    fn test_create_hand_triple_quad() {
+        let test_player = Player {
+            id: 1,
+            cards: vec![],
+        };
+        let kings = [
+            Card::new(Rank::King, Suit::Diamond),
+            Card::new(Rank::King, Suit::Club),
+            Card::new(Rank::King, Suit::Heart),
+            Card::new(Rank::King, Suit::Spade),
+        ];
+
+        let triple = Hand::new(&kings[..3], &test_player).unwrap();
+        assert_eq!(triple.kind, HandType::Triple);
+        let quad = Hand::new(&kings, &test_player).unwrap();
+        assert_eq!(quad.kind, HandType::Quad);
+
+        let mixed = [kings[0], kings[1], Card::new(Rank::Ace, Suit::Spade)];
+        assert!(Hand::new(&mixed, &test_player).is_err());
+
+        // Compared by the strongest card.
+        let aces = [
+            Card::new(Rank::Ace, Suit::Diamond),
+            Card::new(Rank::Ace, Suit::Club),
+            Card::new(Rank::Ace, Suit::Heart),
+        ];
+        let ace_triple = Hand::new(&aces, &test_player).unwrap();
+        let rules = RuleSet::default();
+        assert_eq!(ace_triple.partial_cmp_with(&triple, &rules), Some(Greater));
+        assert_eq!(ace_triple.partial_cmp_with(&quad, &rules), None);
+    }
+
+    #[test]
+    fn test_hand_is_bomb() {
+        let test_player = Player {
+            id: 1,
+            cards: vec![],
+        };
+        let kings = [
+            Card::new(Rank::King, Suit::Diamond),
+            Card::new(Rank::King, Suit::Club),
+            Card::new(Rank::King, Suit::Heart),
+            Card::new(Rank::King, Suit::Spade),
+        ];
+        let quad = Hand::new(&kings, &test_player).unwrap();
+        let bomb = get_test_hand(&test_player, ComboType::Bomb, RelativeStrength::Normal).unwrap();
+        let straight_flush = get_test_hand(
+            &test_player,
+            ComboType::StraightFlush,
+            RelativeStrength::Normal,
+        )
+        .unwrap();
+        let full_house =
+            get_test_hand(&test_player, ComboType::FullHouse, RelativeStrength::Normal).unwrap();
+
+        assert!(quad.is_bomb() && bomb.is_bomb() && straight_flush.is_bomb());
+        assert!(!full_house.is_bomb());
+        assert_eq!(full_house.bomb_strength_with(&RuleSet::default()), None);
+
+        // Four of a kind ranks below a straight flush.
+        let rules = RuleSet::default();
+        assert!(quad.bomb_strength_with(&rules) < straight_flush.bomb_strength_with(&rules));
+    }
+
+    #[test]
+    fn test_create_hand_tien_len() {
+        let rules = RuleSet::tien_len();
+        let test_player = Player {
+            id: 1,
+            cards: vec![],
+        };
+        let run = |ranks: &[Rank]| {
+            ranks
+                .iter()
+                .map(|rank| Card::new(*rank, Suit::Club))
+                .collect::<Vec<Card>>()
+        };
+        let pairs = |ranks: &[Rank]| {
+            ranks
+                .iter()
+                .flat_map(|rank| [Card::new(*rank, Suit::Club), Card::new(*rank, Suit::Heart)])
+                .collect::<Vec<Card>>()
+        };
+
+        let sequence = Hand::with_rules(
+            &run(&[Rank::Nine, Rank::Ten, Rank::Jack]),
+            &test_player,
+            &rules,
+        )
+        .unwrap();
+        assert_eq!(sequence.kind, HandType::Sequence);
+
+        // Five of the same suit in sequence is not a straight flush.
+        let long_sequence = run(&[Rank::Three, Rank::Four, Rank::Five, Rank::Six, Rank::Seven]);
+        let long_sequence = Hand::with_rules(&long_sequence, &test_player, &rules).unwrap();
+        assert_eq!(long_sequence.kind, HandType::Sequence);
+        assert_eq!(long_sequence.combo, ComboType::None);
+
+        // Different lengths can't be compared.
+        assert_eq!(sequence.partial_cmp_with(&long_sequence, &rules), None);
+
+        // 2s can't be part of a sequence.
+        assert!(Hand::with_rules(
+            &run(&[Rank::King, Rank::Ace, Rank::Two]),
+            &test_player,
+            &rules
+        )
+        .is_err());
+
+        let double_sequence = Hand::with_rules(
+            &pairs(&[Rank::Four, Rank::Five, Rank::Six]),
+            &test_player,
+            &rules,
+        )
+        .unwrap();
+        assert_eq!(double_sequence.kind, HandType::DoubleSequence);
+        assert!(Hand::with_rules(
+            &pairs(&[Rank::Four, Rank::Five, Rank::Seven]),
+            &test_player,
+            &rules
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_chops() {
+        let rules = RuleSet::tien_len();
+        let test_player = Player {
+            id: 1,
+            cards: vec![],
+        };
+        let pairs = |ranks: &[Rank]| {
+            let cards = ranks
+                .iter()
+                .flat_map(|rank| [Card::new(*rank, Suit::Club), Card::new(*rank, Suit::Heart)])
+                .collect::<Vec<Card>>();
+            Hand::with_rules(&cards, &test_player, &rules).unwrap()
+        };
+        let single_two =
+            Hand::with_rules(&[Card::new(Rank::Two, Suit::Heart)], &test_player, &rules).unwrap();
+        let pair_twos = pairs(&[Rank::Two]);
+        let three_pairs = pairs(&[Rank::Four, Rank::Five, Rank::Six]);
+        let four_pairs = pairs(&[Rank::Four, Rank::Five, Rank::Six, Rank::Seven]);
+        let quad = Hand::with_rules(
+            &[
+                Card::new(Rank::Nine, Suit::Spade),
+                Card::new(Rank::Nine, Suit::Club),
+                Card::new(Rank::Nine, Suit::Diamond),
+                Card::new(Rank::Nine, Suit::Heart),
+            ],
+            &test_player,
+            &rules,
+        )
+        .unwrap();
+
+        assert!(three_pairs.chops(&single_two));
+        assert!(!three_pairs.chops(&pair_twos));
+        assert!(quad.chops(&single_two));
+        assert!(quad.chops(&three_pairs));
+        assert!(four_pairs.chops(&pair_twos));
+        assert!(four_pairs.chops(&quad));
+        assert!(four_pairs.chops(&three_pairs));
+
+        // Only 2s can be chopped.
+        let single_ace =
+            Hand::with_rules(&[Card::new(Rank::Ace, Suit::Heart)], &test_player, &rules).unwrap();
+        assert!(!quad.chops(&single_ace));
+    }
+
     #[test]
     fn test_create_hand_combo() {
         let test_player = Player {
@@ -562,6 +890,7 @@ pub mod tests {
             cards: vec![Card {
                 rank: Rank::Ace,
                 suit: Suit::Club,
+                deck: 0,
             }],
         };
         if let Err(hand_err) =
@@ -578,6 +907,7 @@ pub mod tests {
             cards: vec![Card {
                 rank: Rank::Ace,
                 suit: Suit::Club,
+                deck: 0,
             }],
         };
         let test_bomb_res = get_test_hand(&test_player, ComboType::Bomb, RelativeStrength::Normal);
@@ -592,9 +922,9 @@ pub mod tests {
         if let (Ok(hand_bomb), Ok(hand_flush), Ok(hand_royal_flush)) =
             (&test_bomb_res, &test_flush_res, &test_royal_flush_res)
         {
-            assert_eq!(Hand::is_flush(&hand_bomb.cards), false);
-            assert_eq!(Hand::is_flush(&hand_flush.cards), true);
-            assert_eq!(Hand::is_flush(&hand_royal_flush.cards), true);
+            assert!(!Hand::is_flush(&hand_bomb.cards));
+            assert!(Hand::is_flush(&hand_flush.cards));
+            assert!(Hand::is_flush(&hand_royal_flush.cards));
         } else {
             if let Err(test_bomb_res) = test_bomb_res {
                 panic!("{} (Bomb)", test_bomb_res)
@@ -613,6 +943,7 @@ pub mod tests {
             cards: vec![Card {
                 rank: Rank::Ace,
                 suit: Suit::Club,
+                deck: 0,
             }],
         };
         let test_bomb_res = get_test_hand(&test_player, ComboType::Bomb, RelativeStrength::Normal);
@@ -627,9 +958,18 @@ pub mod tests {
         if let (Ok(hand_bomb), Ok(hand_straight), Ok(hand_royal_flush)) =
             (&test_bomb_res, &test_straight_res, &test_royal_flush_res)
         {
-            assert_eq!(Hand::is_straight(&hand_bomb.cards), false);
-            assert_eq!(Hand::is_straight(&hand_straight.cards), true);
-            assert_eq!(Hand::is_straight(&hand_royal_flush.cards), true);
+            assert!(!Hand::is_straight(
+                &hand_bomb.cards,
+                &StraightRules::default()
+            ));
+            assert!(Hand::is_straight(
+                &hand_straight.cards,
+                &StraightRules::default()
+            ));
+            assert!(Hand::is_straight(
+                &hand_royal_flush.cards,
+                &StraightRules::default()
+            ));
         } else {
             if let Err(test_bomb_err) = test_bomb_res {
                 panic!("{} (Bomb)", test_bomb_err)
@@ -648,6 +988,7 @@ pub mod tests {
             cards: vec![Card {
                 rank: Rank::Ace,
                 suit: Suit::Club,
+                deck: 0,
             }],
         };
         let test_bomb_res = get_test_hand(&test_player, ComboType::Bomb, RelativeStrength::Normal);
@@ -655,14 +996,11 @@ pub mod tests {
             get_test_hand(&test_player, ComboType::FullHouse, RelativeStrength::Normal);
 
         if let (Ok(hand_bomb), Ok(hand_full_house)) = (&test_bomb_res, &test_full_house_res) {
-            assert_eq!(
-                Hand::is_dupe_combo(&hand_bomb.cards, ComboType::FullHouse),
-                false
-            );
-            assert_eq!(
-                Hand::is_dupe_combo(&hand_full_house.cards, ComboType::FullHouse),
-                true
-            );
+            assert!(!Hand::is_dupe_combo(&hand_bomb.cards, ComboType::FullHouse));
+            assert!(Hand::is_dupe_combo(
+                &hand_full_house.cards,
+                ComboType::FullHouse
+            ));
         } else {
             if let Err(test_bomb_err) = test_bomb_res {
                 panic!("{} (Bomb)", test_bomb_err)
@@ -679,6 +1017,7 @@ pub mod tests {
             cards: vec![Card {
                 rank: Rank::Ace,
                 suit: Suit::Club,
+                deck: 0,
             }],
         };
         let test_bomb_res = get_test_hand(&test_player, ComboType::Bomb, RelativeStrength::Normal);
@@ -686,11 +1025,11 @@ pub mod tests {
             get_test_hand(&test_player, ComboType::FullHouse, RelativeStrength::Normal);
 
         if let (Ok(hand_bomb), Ok(hand_full_house)) = (&test_bomb_res, &test_full_house_res) {
-            assert_eq!(Hand::is_dupe_combo(&hand_bomb.cards, ComboType::Bomb), true);
-            assert_eq!(
-                Hand::is_dupe_combo(&hand_full_house.cards, ComboType::Bomb),
-                false
-            );
+            assert!(Hand::is_dupe_combo(&hand_bomb.cards, ComboType::Bomb));
+            assert!(!Hand::is_dupe_combo(
+                &hand_full_house.cards,
+                ComboType::Bomb
+            ));
         } else {
             if let Err(test_bomb_err) = test_bomb_res {
                 panic!("{} (Bomb)", test_bomb_err)
@@ -707,6 +1046,7 @@ pub mod tests {
             cards: vec![Card {
                 rank: Rank::Ace,
                 suit: Suit::Club,
+                deck: 0,
             }],
         };
         let test_bomb_res = get_test_hand(&test_player, ComboType::Bomb, RelativeStrength::Normal);
@@ -721,9 +1061,18 @@ pub mod tests {
         if let (Ok(hand_bomb), Ok(hand_straight), Ok(hand_royal_flush)) =
             (&test_bomb_res, &test_straight_res, &test_royal_flush_res)
         {
-            assert_eq!(Hand::is_royal_flush(&hand_bomb.cards), false);
-            assert_eq!(Hand::is_royal_flush(&hand_straight.cards), false);
-            assert_eq!(Hand::is_royal_flush(&hand_royal_flush.cards), true);
+            assert!(!Hand::is_royal_flush(
+                &hand_bomb.cards,
+                &StraightRules::default()
+            ));
+            assert!(!Hand::is_royal_flush(
+                &hand_straight.cards,
+                &StraightRules::default()
+            ));
+            assert!(Hand::is_royal_flush(
+                &hand_royal_flush.cards,
+                &StraightRules::default()
+            ));
         } else {
             if let Err(test_bomb_err) = test_bomb_res {
                 panic!("{} (Bomb)", test_bomb_err)
@@ -742,6 +1091,7 @@ pub mod tests {
             cards: vec![Card {
                 rank: Rank::Ace,
                 suit: Suit::Club,
+                deck: 0,
             }],
         };
         let hand_straight =
@@ -761,36 +1111,142 @@ pub mod tests {
             RelativeStrength::Normal,
         );
 
-        if let Ok(straight) = hand_straight {
-            if let Ok(straight_strength) = straight.strength() {
-                assert_eq!(27.0, straight_strength)
-            }
-        };
-        if let Ok(flush) = hand_flush {
-            if let Ok(flush_strength) = flush.strength() {
-                assert_eq!(2026.1201, flush_strength)
-            }
+        // Each combo type outranks the one before it.
+        let strengths = [
+            hand_straight,
+            hand_flush,
+            hand_full_house,
+            hand_bomb,
+            hand_straight_flush,
+            hand_royal_flush,
+        ]
+        .into_iter()
+        .map(|hand| hand.unwrap().strength().unwrap())
+        .collect::<Vec<u64>>();
+        assert!(strengths.windows(2).all(|pair| pair[0] < pair[1]));
+
+        // A single is keyed by its exact card value.
+        let single = Hand::new(&[Card::new(Rank::Four, Suit::Spade)], &test_player).unwrap();
+        assert_eq!(single.strength().unwrap(), 24);
+    }
+
+    #[test]
+    fn test_straight_rules() {
+        let test_player = Player {
+            id: 1,
+            cards: vec![],
         };
-        if let Ok(full_house) = hand_full_house {
-            if let Ok(full_house_strength) = full_house.strength() {
-                assert_eq!(22958.254, full_house_strength)
-            }
+        let straight = |ranks: [Rank; 5], suit: Suit| ranks.map(|rank| Card::new(rank, suit));
+        let mut ace_low = straight(
+            [Rank::Ace, Rank::Two, Rank::Three, Rank::Four, Rank::Five],
+            Suit::Club,
+        );
+        ace_low[0].suit = Suit::Heart;
+        let mut ten_high = straight(
+            [Rank::Six, Rank::Seven, Rank::Eight, Rank::Nine, Rank::Ten],
+            Suit::Club,
+        );
+        ten_high[0].suit = Suit::Heart;
+
+        // A-2-3-4-5 isn't a straight in standard rules.
+        assert!(Hand::new(&ace_low, &test_player).is_err());
+
+        let mut rules = RuleSet {
+            straights: StraightRules {
+                allow_ace_low: true,
+                allow_two_low: true,
+                ranking: StraightRanking::Sequence,
+                ..Default::default()
+            },
+            ..Default::default()
         };
-        if let Ok(bomb) = hand_bomb {
-            if let Ok(bomb_strength) = bomb.strength() {
-                assert_eq!(669462.6, bomb_strength)
-            }
+        let ace_low_hand = Hand::with_rules(&ace_low, &test_player, &rules).unwrap();
+        let ten_high = Hand::with_rules(&ten_high, &test_player, &rules).unwrap();
+        assert_eq!(ace_low_hand.combo, ComboType::Straight);
+        assert_eq!(
+            ten_high.partial_cmp_with(&ace_low_hand, &rules),
+            Some(Greater)
+        );
+
+        rules.straights.ranking = StraightRanking::TopCard;
+        assert_eq!(ten_high.partial_cmp_with(&ace_low_hand, &rules), Some(Less));
+
+        // 2-3-4-5-6 in one suit is the royal flush when wraparounds rank highest.
+        rules.straights.ranking = StraightRanking::WrapHighest;
+        let two_low = straight(
+            [Rank::Two, Rank::Three, Rank::Four, Rank::Five, Rank::Six],
+            Suit::Diamond,
+        );
+        let two_low = Hand::with_rules(&two_low, &test_player, &rules).unwrap();
+        assert_eq!(two_low.combo, ComboType::RoyalFlush);
+        let ace_high = straight(
+            [Rank::Ten, Rank::Jack, Rank::Queen, Rank::King, Rank::Ace],
+            Suit::Spade,
+        );
+        let ace_high = Hand::with_rules(&ace_high, &test_player, &rules).unwrap();
+        assert_eq!(ace_high.combo, ComboType::StraightFlush);
+    }
+
+    #[test]
+    fn test_flush_ranking() {
+        let test_player = Player {
+            id: 1,
+            cards: vec![],
         };
-        if let Ok(straight_flush) = hand_straight_flush {
-            if let Ok(straight_flush_strength) = straight_flush.strength() {
-                assert_eq!(19521532.0, straight_flush_strength)
-            }
+        let read_flush = |test_file: &str| {
+            let cards: Vec<Card> = from_reader(File::open(test_file).unwrap()).unwrap();
+            Hand::new(&cards, &test_player).unwrap()
         };
-        if let Ok(royal_flush) = hand_royal_flush {
-            if let Ok(royal_flush_strength) = royal_flush.strength() {
-                assert_eq!(5328542400000.0, royal_flush_strength)
-            }
+        // 9♠ 7♠ 6♠ 5♠ 4♠
+        let flush = read_flush("./test/hand_flush.json");
+        // 9♣ 7♣ 6♣ 5♣ 4♣
+        let flush_weaker = read_flush("./test/hand_flush_weaker.json");
+        // K♣ 10♣ 8♣ 5♣ 3♣
+        let flush_high_club = read_flush("./test/hand_flush_high_club.json");
+        // 9♦ 8♦ 6♦ 4♦ 3♦
+        let flush_second_card = read_flush("./test/hand_flush_second_card.json");
+
+        let cmp = |hand_1: &Hand, hand_2: &Hand, flush_ranking: FlushRanking| {
+            let rules = RuleSet {
+                flush_ranking,
+                ..Default::default()
+            };
+            hand_1.partial_cmp_with(hand_2, &rules).unwrap()
         };
+
+        // Identical ranks are decided by suit in every variant.
+        for flush_ranking in [
+            FlushRanking::TopCard,
+            FlushRanking::SuitFirst,
+            FlushRanking::CardByCard,
+        ] {
+            assert_eq!(cmp(&flush, &flush_weaker, flush_ranking), Greater);
+        }
+
+        assert_eq!(
+            cmp(&flush_high_club, &flush, FlushRanking::TopCard),
+            Greater
+        );
+        assert_eq!(cmp(&flush_second_card, &flush, FlushRanking::TopCard), Less);
+
+        assert_eq!(cmp(&flush_high_club, &flush, FlushRanking::SuitFirst), Less);
+        assert_eq!(
+            cmp(&flush_high_club, &flush_weaker, FlushRanking::SuitFirst),
+            Greater
+        );
+        assert_eq!(
+            cmp(&flush_second_card, &flush, FlushRanking::SuitFirst),
+            Less
+        );
+
+        assert_eq!(
+            cmp(&flush_high_club, &flush, FlushRanking::CardByCard),
+            Greater
+        );
+        assert_eq!(
+            cmp(&flush_second_card, &flush, FlushRanking::CardByCard),
+            Greater
+        );
     }
 
     #[test]
@@ -800,6 +1256,7 @@ pub mod tests {
             cards: vec![Card {
                 rank: Rank::Ace,
                 suit: Suit::Club,
+                deck: 0,
             }],
         };
         let hand_straight =
@@ -815,23 +1272,27 @@ pub mod tests {
         let hand_full_house =
             get_test_hand(&test_player, ComboType::FullHouse, RelativeStrength::Normal);
 
+        let rules = RuleSet::default();
         if let (Ok(straight), Ok(straight_stronger)) = (hand_straight, hand_straight_stronger) {
-            assert_eq!(straight_stronger > straight, true)
+            assert_eq!(
+                straight_stronger.partial_cmp_with(&straight, &rules),
+                Some(Greater)
+            )
         };
         if let (Ok(flush), Ok(flush_weaker)) = (&hand_flush, hand_flush_weaker) {
-            assert_eq!(flush_weaker < *flush, true)
+            assert_eq!(flush_weaker.partial_cmp_with(flush, &rules), Some(Less))
         };
         if let (Ok(full_house), Ok(flush)) = (hand_full_house, hand_flush) {
-            assert_eq!(full_house > flush, true)
+            assert!(full_house.strength().unwrap() > flush.strength().unwrap())
         };
     }
 
     #[test]
-    #[should_panic]
     fn test_invalid_combo_cmp() {
         let single_cards = vec![Card {
             rank: Rank::Ace,
             suit: Suit::Club,
+            deck: 0,
         }];
         let test_player = Player {
             id: 1,
@@ -840,8 +1301,146 @@ pub mod tests {
         let hand_straight =
             get_test_hand(&test_player, ComboType::Straight, RelativeStrength::Normal);
 
-        let hand_single = Hand::new(&single_cards, &test_player);
+        let hand_single = Hand::new(&single_cards, &test_player).unwrap();
+        let hand_straight = hand_straight.unwrap();
+
+        // Hands of different kinds cannot be compared.
+        assert_eq!(
+            hand_single.partial_cmp_with(&hand_straight, &RuleSet::default()),
+            None
+        );
+    }
 
-        let _ = hand_single > hand_straight;
+    #[test]
+    fn test_cmp_with_rules() {
+        let test_player = Player {
+            id: 1,
+            cards: vec![],
+        };
+        let double_diamond = Hand::new(
+            &[
+                Card::new(Rank::King, Suit::Diamond),
+                Card::new(Rank::King, Suit::Club),
+            ],
+            &test_player,
+        )
+        .unwrap();
+        let double_spade = Hand::new(
+            &[
+                Card::new(Rank::King, Suit::Spade),
+                Card::new(Rank::King, Suit::Heart),
+            ],
+            &test_player,
+        )
+        .unwrap();
+
+        assert_eq!(
+            double_diamond.partial_cmp_with(&double_spade, &RuleSet::big_two()),
+            Some(Less)
+        );
+        assert_eq!(
+            double_diamond.partial_cmp_with(&double_spade, &RuleSet::pusoy_dos()),
+            Some(Greater)
+        );
+        assert_eq!(
+            double_diamond.partial_cmp_with(&double_spade, &RuleSet::tien_len()),
+            Some(Less)
+        );
+    }
+
+    #[test]
+    fn test_get_cards_with_rules() {
+        let test_player = Player {
+            id: 1,
+            cards: vec![],
+        };
+        let full_house = Hand::new(
+            &[
+                Card::new(Rank::King, Suit::Diamond),
+                Card::new(Rank::King, Suit::Club),
+                Card::new(Rank::King, Suit::Spade),
+                Card::new(Rank::Five, Suit::Heart),
+                Card::new(Rank::Five, Suit::Spade),
+            ],
+            &test_player,
+        )
+        .unwrap();
+        let strongest = |rules: &RuleSet| {
+            full_house.get_cards_with(
+                &[CardFilter::MostFrequentRanks, CardFilter::Strongest],
+                rules,
+            )
+        };
+
+        // Spades are highest in Big Two but diamonds are highest in Pusoy Dos.
+        assert_eq!(
+            strongest(&RuleSet::big_two()),
+            Some(vec![Card::new(Rank::King, Suit::Spade)])
+        );
+        assert_eq!(
+            strongest(&RuleSet::pusoy_dos()),
+            Some(vec![Card::new(Rank::King, Suit::Diamond)])
+        );
+        assert_eq!(
+            full_house.get_cards_with(&[CardFilter::Weakest], &RuleSet::pusoy_dos()),
+            Some(vec![Card::new(Rank::Five, Suit::Spade)])
+        );
+    }
+
+    #[test]
+    fn test_strength_key_bytes() {
+        let test_player = Player {
+            id: 1,
+            cards: vec![],
+        };
+        let rules = RuleSet {
+            flush_ranking: FlushRanking::CardByCard,
+            ..Default::default()
+        };
+        let ranks = [Rank::King, Rank::Ten, Rank::Eight, Rank::Six, Rank::Four];
+        let lower_ranks = [
+            Rank::Queen,
+            Rank::Nine,
+            Rank::Seven,
+            Rank::Five,
+            Rank::Three,
+        ];
+        let flush = |ranks: &[Rank], suit: Suit| {
+            let cards = ranks
+                .iter()
+                .map(|rank| Card::new(*rank, suit))
+                .collect::<Vec<Card>>();
+            Hand::new(&cards, &test_player).unwrap()
+        };
+        let strongest = flush(&ranks, Suit::Spade);
+        let key = strongest.strength_with(&rules).unwrap();
+
+        // Each card decides one byte, from the top card down.
+        for (idx, lower_rank) in lower_ranks.iter().enumerate() {
+            let mut weaker_ranks = ranks;
+            weaker_ranks[idx] = *lower_rank;
+            let weaker = flush(&weaker_ranks, Suit::Spade);
+
+            assert_eq!(
+                key - weaker.strength_with(&rules).unwrap(),
+                1 << (8 * (5 - idx))
+            );
+            assert_eq!(strongest.partial_cmp_with(&weaker, &rules), Some(Greater));
+        }
+
+        // Suit breaks ties between the same ranks in the lowest byte.
+        let hearts = flush(&ranks, Suit::Heart);
+        assert_eq!(key - hearts.strength_with(&rules).unwrap(), 1);
+
+        // Hands with the same deciding values tie.
+        let top_card_rules = RuleSet::default();
+        let mut other_ranks = lower_ranks;
+        other_ranks[0] = Rank::King;
+        let other = flush(&other_ranks, Suit::Spade);
+        assert_eq!(
+            strongest.partial_cmp_with(&other, &top_card_rules),
+            Some(Equal)
+        );
+        assert_ne!(strongest, other);
     }
 }
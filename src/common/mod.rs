@@ -1,8 +1,11 @@
+//! Card model shared by the game engine.
+
 pub mod card;
 pub mod deck;
 pub mod error;
 pub mod hand;
 pub mod player;
 pub mod rank;
+pub mod rules;
 pub mod stack;
 pub mod suit;
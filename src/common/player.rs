@@ -1,7 +1,73 @@
-use crate::common::card::Card;
+use crate::common::{card::Card, error::GameError};
+use itertools::Itertools;
 
+/// A seat at the table and the cards it holds.
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Player {
     pub id: usize,
     pub cards: Vec<Card>,
 }
+
+impl Player {
+    /// Check that the player holds each of the `cards` exactly once.
+    pub fn check_holds(&self, cards: &[Card]) -> Result<(), GameError> {
+        if let Some(card) = cards.iter().duplicates().next() {
+            return Err(GameError::DuplicateCard(format!(
+                "{card:?} was played more than once."
+            )));
+        }
+        if let Some(card) = cards.iter().find(|card| !self.cards.contains(card)) {
+            return Err(GameError::CardNotHeld(format!(
+                "Player {} does not hold {card:?}.",
+                self.id + 1
+            )));
+        }
+        Ok(())
+    }
+
+    /// Remove the `cards` from the player's hand.
+    ///
+    /// Nothing is removed unless the player holds every card exactly once.
+    pub fn remove(&mut self, cards: &[Card]) -> Result<&Player, GameError> {
+        self.check_holds(cards)?;
+        self.cards.retain(|card| !cards.contains(card));
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Player;
+    use crate::common::{card::Card, error::GameError, rank::Rank, suit::Suit};
+
+    #[test]
+    fn test_remove() {
+        let three_diamonds = Card::new(Rank::Three, Suit::Diamond);
+        let four_clubs = Card::new(Rank::Four, Suit::Club);
+        let mut player = Player {
+            id: 0,
+            cards: vec![three_diamonds, four_clubs],
+        };
+
+        assert!(matches!(
+            player.remove(&[three_diamonds, three_diamonds]),
+            Err(GameError::DuplicateCard(_))
+        ));
+        assert!(matches!(
+            player.remove(&[three_diamonds, Card::new(Rank::Ace, Suit::Heart)]),
+            Err(GameError::CardNotHeld(_))
+        ));
+        // Same rank and suit from another deck is a different card.
+        assert!(matches!(
+            player.remove(&[Card {
+                deck: 1,
+                ..four_clubs
+            }]),
+            Err(GameError::CardNotHeld(_))
+        ));
+        assert_eq!(player.cards.len(), 2);
+
+        player.remove(&[three_diamonds]).unwrap();
+        assert_eq!(player.cards, vec![four_clubs]);
+    }
+}
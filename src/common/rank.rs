@@ -60,7 +60,7 @@ impl Ord for Rank {
 
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         // Compare rank based on value.
-        (*self as usize).partial_cmp(&(*other as usize)).unwrap()
+        (*self as usize).cmp(&(*other as usize))
     }
 }
 
@@ -86,10 +86,11 @@ impl PartialOrd for Rank {
     }
 
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some((*self as usize).cmp(&(*other as usize)))
+        Some(self.cmp(other))
     }
 }
 
+#[cfg(test)]
 mod tests {
     use super::Rank;
 
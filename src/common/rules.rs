@@ -0,0 +1,391 @@
+use crate::common::{
+    card::Card,
+    hand::{Hand, HandType},
+    rank::Rank,
+    suit::Suit,
+};
+use itertools::Itertools;
+use std::cmp::Ordering::{self, Greater};
+
+/// Families of climbing games played with the engine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Variant {
+    /// Big 2 and its regional variants with five-card poker combos.
+    #[default]
+    BigTwo,
+    /// Tiến lên. Sequences of any length and double sequences replace five-card combos.
+    ///
+    /// Reference: https://www.pagat.com/climbing/tienlen.html
+    TienLen,
+}
+
+/// How straights that wrap around from 2 to 3 are ranked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StraightRanking {
+    /// By the highest card. Wraparounds rank with J-Q-K-A-2.
+    #[default]
+    TopCard,
+    /// By the top of the sequence. A-2-3-4-5 is the lowest straight, followed by 2-3-4-5-6.
+    Sequence,
+    /// Wraparounds beat all other straights. 2-3-4-5-6 is the highest, followed by A-2-3-4-5.
+    WrapHighest,
+}
+
+/// Rules for which straights are valid and how they rank.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StraightRules {
+    /// Allow 2s in straights. J-Q-K-A-2 is a straight.
+    pub allow_twos: bool,
+    /// Allow A-2-3-4-5. Requires `allow_twos`.
+    pub allow_ace_low: bool,
+    /// Allow 2-3-4-5-6. Requires `allow_twos`.
+    pub allow_two_low: bool,
+    pub ranking: StraightRanking,
+}
+
+impl Default for StraightRules {
+    fn default() -> Self {
+        StraightRules {
+            allow_twos: true,
+            allow_ace_low: false,
+            allow_two_low: false,
+            ranking: StraightRanking::TopCard,
+        }
+    }
+}
+
+impl StraightRules {
+    /// A-2-3-4-5 ordered by rank value.
+    const ACE_LOW: [Rank; 5] = [Rank::Three, Rank::Four, Rank::Five, Rank::Ace, Rank::Two];
+    /// 2-3-4-5-6 ordered by rank value.
+    const TWO_LOW: [Rank; 5] = [Rank::Three, Rank::Four, Rank::Five, Rank::Six, Rank::Two];
+
+    /// Rank a straight of `cards`.
+    ///
+    /// Returns the position of the straight, higher is stronger, and the rank of the card that breaks ties.
+    /// Returns `None` if the cards aren't a straight under these rules.
+    pub fn rank_straight(&self, cards: &[Card]) -> Option<(usize, Rank)> {
+        if cards.len() != 5 {
+            return None;
+        }
+        let ranks = cards
+            .iter()
+            .map(|card| card.rank)
+            .sorted_by_key(|rank| *rank as usize)
+            .collect_vec();
+        if ranks.contains(&Rank::Two) && !self.allow_twos {
+            return None;
+        }
+
+        let is_consecutive = ranks
+            .iter()
+            .tuple_windows()
+            .all(|(rank_1, rank_2)| *rank_2 as usize - *rank_1 as usize == 1);
+        let wrap_top = if is_consecutive {
+            return Some((ranks[4] as usize, ranks[4]));
+        } else if self.allow_ace_low && ranks == Self::ACE_LOW {
+            Rank::Five
+        } else if self.allow_two_low && ranks == Self::TWO_LOW {
+            Rank::Six
+        } else {
+            return None;
+        };
+
+        Some(match self.ranking {
+            StraightRanking::TopCard => (Rank::Two as usize, Rank::Two),
+            StraightRanking::Sequence => (wrap_top as usize, wrap_top),
+            // Rank above J-Q-K-A-2. 2-3-4-5-6 over A-2-3-4-5.
+            StraightRanking::WrapHighest => (Rank::Two as usize + wrap_top as usize, Rank::Two),
+        })
+    }
+
+    /// Ranks of a royal flush. The highest wraparound if they rank highest, otherwise 10-J-Q-K-A.
+    pub fn royal_ranks(&self) -> [Rank; 5] {
+        let wraps_highest = self.ranking == StraightRanking::WrapHighest && self.allow_twos;
+        if wraps_highest && self.allow_two_low {
+            Self::TWO_LOW
+        } else if wraps_highest && self.allow_ace_low {
+            Self::ACE_LOW
+        } else {
+            [Rank::Ten, Rank::Jack, Rank::Queen, Rank::King, Rank::Ace]
+        }
+    }
+}
+
+/// How flushes are compared.
+///
+/// Reference: https://www.pagat.com/climbing/bigtwo.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FlushRanking {
+    /// By the highest card.
+    #[default]
+    TopCard,
+    /// By suit, then by the highest card.
+    SuitFirst,
+    /// By the rank of each card from the highest down, then by the suit.
+    CardByCard,
+}
+
+/// Rules used to compare `Card`s and `Hand`s.
+///
+/// Carried by the `Game` so regional variants can be played with the same engine.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleSet {
+    /// Suits ordered from weakest to strongest.
+    pub suit_order: [Suit; 4],
+    /// How hands are classified and which hands can chop others.
+    pub variant: Variant,
+    /// Allow three cards of the same rank to be played as a hand.
+    pub allow_triples: bool,
+    /// Allow four cards of the same rank to be played as a hand.
+    pub allow_quads: bool,
+    /// Allow bombs to be played on top of any trick that isn't a bomb.
+    pub bomb_interrupts: bool,
+    /// Which straights are valid and how they rank.
+    pub straights: StraightRules,
+    /// How flushes are compared.
+    pub flush_ranking: FlushRanking,
+    /// Player before someone with one card left must lead their highest single when leading a single.
+    pub last_card: bool,
+}
+
+impl Default for RuleSet {
+    fn default() -> Self {
+        RuleSet::big_two()
+    }
+}
+
+impl RuleSet {
+    /// Standard Big 2 rules. Spades > Hearts > Clubs > Diamonds.
+    pub fn big_two() -> RuleSet {
+        RuleSet {
+            suit_order: [Suit::Diamond, Suit::Club, Suit::Heart, Suit::Spade],
+            variant: Variant::BigTwo,
+            allow_triples: false,
+            allow_quads: false,
+            bomb_interrupts: false,
+            straights: StraightRules::default(),
+            flush_ranking: FlushRanking::TopCard,
+            last_card: false,
+        }
+    }
+
+    /// Pusoy Dos rules. Diamonds > Hearts > Spades > Clubs.
+    pub fn pusoy_dos() -> RuleSet {
+        RuleSet {
+            suit_order: [Suit::Club, Suit::Spade, Suit::Heart, Suit::Diamond],
+            ..RuleSet::big_two()
+        }
+    }
+
+    /// Tiến lên rules. Hearts > Diamonds > Clubs > Spades.
+    pub fn tien_len() -> RuleSet {
+        RuleSet {
+            suit_order: [Suit::Spade, Suit::Club, Suit::Diamond, Suit::Heart],
+            variant: Variant::TienLen,
+            allow_triples: true,
+            allow_quads: true,
+            ..RuleSet::big_two()
+        }
+    }
+
+    /// Check if hands of some `kind` can be played.
+    pub fn allows(&self, kind: HandType) -> bool {
+        match kind {
+            HandType::Triple => self.allow_triples,
+            HandType::Quad => self.allow_quads,
+            HandType::Combo => self.variant == Variant::BigTwo,
+            HandType::Sequence | HandType::DoubleSequence => self.variant == Variant::TienLen,
+            HandType::Single | HandType::Double => true,
+            HandType::None => false,
+        }
+    }
+
+    /// Check that `new_hand` is allowed by the rules and beats the `previous_hand`, if any.
+    pub fn check_play(
+        &self,
+        new_hand: &Hand,
+        previous_hand: Option<&Hand>,
+    ) -> Result<(), &'static str> {
+        if !self.allows(new_hand.kind) {
+            return Err("Hand kind is not allowed by the rules.");
+        }
+
+        // Check that hand beats previously based hand.
+        if let Some(previous_hand) = previous_hand {
+            self.beats(new_hand, previous_hand)?;
+        }
+
+        Ok(())
+    }
+
+    /// Check that `new_hand` can be played on top of `previous_hand`.
+    pub fn beats(&self, new_hand: &Hand, previous_hand: &Hand) -> Result<(), &'static str> {
+        // Bombs and chops can interrupt a trick of any kind.
+        if self.variant == Variant::TienLen && new_hand.chops(previous_hand) {
+            return Ok(());
+        }
+        let interrupts = self.bomb_interrupts && new_hand.is_bomb();
+
+        if new_hand.kind != previous_hand.kind && !interrupts {
+            return Err("Current hand kind doesn't match previous stack kind.");
+        }
+
+        if interrupts && previous_hand.is_bomb() {
+            // Only a stronger bomb beats a bomb.
+            if new_hand.bomb_strength_with(self) <= previous_hand.bomb_strength_with(self) {
+                return Err("Previous bomb is stronger than or equal to added bomb.");
+            }
+        } else if !interrupts {
+            if new_hand.cards.len() != previous_hand.cards.len() {
+                return Err("Current hand length doesn't match previous hand length.");
+            }
+            // Hands of identical cards from different decks don't beat each other.
+            if new_hand.partial_cmp_with(previous_hand, self) != Some(Greater) {
+                return Err("Previous hand is stronger than or equal to added hand.");
+            }
+        }
+        Ok(())
+    }
+
+    /// Highest single in `cards` that must be led under the `last_card` rule, if any.
+    ///
+    /// Only applies when leading a single while the next player holds `n_cards_next` cards.
+    pub fn last_card_single(
+        &self,
+        cards: &[Card],
+        is_leading: bool,
+        n_cards_next: usize,
+    ) -> Option<Card> {
+        if !self.last_card || !is_leading || n_cards_next != 1 {
+            return None;
+        }
+        cards
+            .iter()
+            .max_by(|card_1, card_2| self.cmp_cards(card_1, card_2))
+            .copied()
+    }
+
+    /// Value of a suit from 1 (weakest) to 4 (strongest).
+    pub fn suit_value(&self, suit: Suit) -> usize {
+        self.suit_order
+            .iter()
+            .position(|ordered_suit| *ordered_suit == suit)
+            .map_or(0, |idx| idx + 1)
+    }
+
+    /// Assign value of card based on rank and suit.
+    pub fn card_value(&self, card: &Card) -> usize {
+        // rank (ace: 12) * 10 + (strongest suit: 4) -> 124
+        (card.rank as usize) * 10 + self.suit_value(card.suit)
+    }
+
+    /// Compare cards based on value. Break ties between identical cards by deck.
+    pub fn cmp_cards(&self, card_1: &Card, card_2: &Card) -> Ordering {
+        self.card_value(card_1)
+            .cmp(&self.card_value(card_2))
+            .then(card_1.deck.cmp(&card_2.deck))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RuleSet, StraightRanking, StraightRules};
+    use crate::common::{card::Card, hand::HandType, rank::Rank, suit::Suit};
+    use std::cmp::Ordering::{Greater, Less};
+
+    #[test]
+    fn test_suit_order() {
+        let diamond = Card::new(Rank::Ace, Suit::Diamond);
+        let heart = Card::new(Rank::Ace, Suit::Heart);
+        let spade = Card::new(Rank::Ace, Suit::Spade);
+
+        let big_two = RuleSet::big_two();
+        assert_eq!(big_two.cmp_cards(&diamond, &spade), Less);
+        assert_eq!(big_two.cmp_cards(&heart, &spade), Less);
+
+        let pusoy_dos = RuleSet::pusoy_dos();
+        assert_eq!(pusoy_dos.cmp_cards(&diamond, &spade), Greater);
+        assert_eq!(pusoy_dos.cmp_cards(&diamond, &heart), Greater);
+
+        let tien_len = RuleSet::tien_len();
+        assert_eq!(tien_len.cmp_cards(&heart, &diamond), Greater);
+        assert_eq!(tien_len.cmp_cards(&spade, &diamond), Less);
+    }
+
+    #[test]
+    fn test_rank_before_suit() {
+        let rules = RuleSet::pusoy_dos();
+        let two_clubs = Card::new(Rank::Two, Suit::Club);
+        let ace_diamonds = Card::new(Rank::Ace, Suit::Diamond);
+
+        assert_eq!(rules.cmp_cards(&two_clubs, &ace_diamonds), Greater);
+        assert_eq!(rules.suit_value(Suit::Club), 1);
+        assert_eq!(rules.suit_value(Suit::Diamond), 4);
+    }
+
+    #[test]
+    fn test_allows() {
+        let rules = RuleSet::default();
+        assert!(rules.allows(HandType::Combo));
+        assert!(!rules.allows(HandType::Triple));
+        assert!(!rules.allows(HandType::Quad));
+
+        let triples = RuleSet {
+            allow_triples: true,
+            ..RuleSet::pusoy_dos()
+        };
+        assert!(triples.allows(HandType::Triple));
+        assert!(!triples.allows(HandType::Quad));
+
+        let tien_len = RuleSet::tien_len();
+        assert!(tien_len.allows(HandType::Quad));
+        assert!(tien_len.allows(HandType::DoubleSequence));
+        assert!(!tien_len.allows(HandType::Combo));
+    }
+
+    #[test]
+    fn test_rank_straight() {
+        let straight = |ranks: [Rank; 5]| ranks.map(|rank| Card::new(rank, Suit::Club));
+        let low = straight([Rank::Three, Rank::Four, Rank::Five, Rank::Six, Rank::Seven]);
+        let two_high = straight([Rank::Jack, Rank::Queen, Rank::King, Rank::Ace, Rank::Two]);
+        let ace_low = straight([Rank::Ace, Rank::Two, Rank::Three, Rank::Four, Rank::Five]);
+        let two_low = straight([Rank::Two, Rank::Three, Rank::Four, Rank::Five, Rank::Six]);
+        let wrap = straight([Rank::Queen, Rank::King, Rank::Ace, Rank::Two, Rank::Three]);
+
+        let rules = StraightRules::default();
+        assert!(rules.rank_straight(&low).is_some());
+        assert!(rules.rank_straight(&two_high).is_some());
+        assert!(rules.rank_straight(&ace_low).is_none());
+        assert!(rules.rank_straight(&two_low).is_none());
+        assert!(rules.rank_straight(&wrap).is_none());
+
+        let no_twos = StraightRules {
+            allow_twos: false,
+            allow_ace_low: true,
+            ..Default::default()
+        };
+        assert!(no_twos.rank_straight(&two_high).is_none());
+        assert!(no_twos.rank_straight(&ace_low).is_none());
+
+        let sequence = StraightRules {
+            allow_ace_low: true,
+            allow_two_low: true,
+            ranking: StraightRanking::Sequence,
+            ..Default::default()
+        };
+        assert!(sequence.rank_straight(&ace_low) < sequence.rank_straight(&two_low));
+        assert!(sequence.rank_straight(&two_low) < sequence.rank_straight(&low));
+
+        let wrap_highest = StraightRules {
+            ranking: StraightRanking::WrapHighest,
+            ..sequence
+        };
+        assert!(wrap_highest.rank_straight(&two_high) < wrap_highest.rank_straight(&ace_low));
+        assert!(wrap_highest.rank_straight(&ace_low) < wrap_highest.rank_straight(&two_low));
+        assert_eq!(
+            wrap_highest.royal_ranks(),
+            [Rank::Three, Rank::Four, Rank::Five, Rank::Six, Rank::Two]
+        );
+    }
+}
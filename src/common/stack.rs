@@ -1,23 +1,44 @@
 use crate::common::{
     card::Card,
-    hand::{ComboType, Gauge, Hand, HandType},
+    hand::{ComboType, Hand, HandType},
     player::Player,
+    rules::RuleSet,
 };
-use itertools::Itertools;
 
-#[derive(Debug)]
+/// The pile of hands played in the current trick.
+///
+/// A trick ends once every other active player has passed on the last hand played.
+#[derive(Debug, Clone)]
 pub struct CardStack {
     pub stack: Vec<Hand>,
     pub kind: HandType,
     pub combo: ComboType,
+    /// Players that passed, in order, since the last hand was played.
+    pub passes: Vec<usize>,
+    /// Rules used to compare hands.
+    pub rules: RuleSet,
+}
+
+impl Default for CardStack {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl CardStack {
+    /// Create an empty stack using the standard `RuleSet`.
     pub fn new() -> CardStack {
+        CardStack::with_rules(RuleSet::default())
+    }
+
+    /// Create an empty stack that compares hands using the given `rules`.
+    pub fn with_rules(rules: RuleSet) -> CardStack {
         CardStack {
             stack: Vec::new(),
             kind: HandType::None,
             combo: ComboType::None,
+            passes: Vec::new(),
+            rules,
         }
     }
 
@@ -25,43 +46,59 @@ impl CardStack {
     ///
     /// Once a hand is added, the kind of hand is set and must be maintained until the stack is cleared.
     pub fn add(&mut self, hand: &[Card], player: &Player) -> Result<&CardStack, &'static str> {
-        let hand_res = Hand::new(hand, player);
-
-        if let Ok(new_hand) = hand_res {
-            // Check that added hand is the same as previous hand kind.
-            if self.kind != HandType::None && self.kind != new_hand.kind {
-                return Err("Current hand kind doesn't match previous stack kind.");
-            }
+        let new_hand = Hand::with_rules(hand, player, &self.rules)?;
+        self.check(&new_hand)?;
+
+        // Set the stack kind based on new hand added. Bombs switch the kind of the stack.
+        self.kind = new_hand.kind;
+        // And set combotype if applicable.
+        self.combo = new_hand.combo;
+        // Add hand to stack once validated.
+        self.stack.push(new_hand);
+        // Other players must respond to the new hand.
+        self.passes.clear();
+
+        Ok(self)
+    }
 
-            // Set the stack kind based on new hand added.
-            self.kind = new_hand.kind;
-
-            // Check that hand beats previously based hand.
-            if let Some(previous_hand) = self.stack.last() {
-                match self.kind {
-                    HandType::Single | HandType::Double | HandType::Combo => {
-                        if new_hand < *previous_hand {
-                            return Err("Previous hand is stronger than added hand.");
-                        }
-                    }
-                    _ => return Err("Invalid stack kind."),
-                }
-            }
-            // And set combotype if applicable.
-            self.combo = new_hand.combo;
-            // Add hand to stack once validated.
-            self.stack.push(new_hand);
-
-            Ok(self)
-        } else {
-            Err(hand_res.unwrap_err())
+    /// Record a player passing on the last hand played.
+    ///
+    /// The player leading a new trick, the player that played the last hand and players that already passed cannot pass.
+    pub fn pass(&mut self, player: &Player) -> Result<&CardStack, &'static str> {
+        if self.stack.is_empty() {
+            return Err("Cannot pass when leading a new hand.");
+        }
+        if self.leader() == Some(player.id) {
+            return Err("Cannot pass on your own hand.");
+        }
+        if self.passes.contains(&player.id) {
+            return Err("Player already passed on this hand.");
         }
+        self.passes.push(player.id);
+        Ok(self)
+    }
+
+    /// Player that played the last hand and will lead the next trick, if any.
+    pub fn leader(&self) -> Option<usize> {
+        self.stack.last().map(|hand| hand.player)
+    }
+
+    /// Check if every other of the `n_active` players has passed on the last hand played.
+    pub fn is_finished(&self, n_active: usize) -> bool {
+        !self.stack.is_empty() && self.passes.len() + 1 >= n_active
+    }
+
+    /// Check that a hand can be added to the stack without adding it.
+    pub fn check(&self, new_hand: &Hand) -> Result<(), &'static str> {
+        self.rules.check_play(new_hand, self.stack.last())
     }
 
     /// Clear the stack of cards.
     pub fn clear(&mut self) -> &CardStack {
         self.stack.clear();
+        self.passes.clear();
         self.kind = HandType::None;
+        self.combo = ComboType::None;
         self
     }
 }
@@ -75,16 +112,10 @@ mod tests {
     };
     use crate::common::{rank::Rank, suit::Suit};
     use crate::logic::combo::get_dupes;
+    use itertools::Itertools;
 
     #[test]
     fn test_create_stack() {
-        let test_player = Player {
-            id: 1,
-            cards: vec![Card {
-                rank: Rank::Ace,
-                suit: Suit::Club,
-            }],
-        };
         let new_stack = CardStack::new();
         assert_eq!(new_stack.kind, HandType::None)
     }
@@ -120,7 +151,7 @@ mod tests {
 
         if let Some(doubles) = get_dupes(&test_player.cards, 2) {
             for double in doubles.iter().sorted() {
-                if let (Some(card_1), Some(card_2)) = (double.get(0), double.get(1)) {
+                if let (Some(card_1), Some(card_2)) = (double.first(), double.get(1)) {
                     new_stack.add(&[*card_1, *card_2], &test_player).unwrap();
                 }
             }
@@ -134,6 +165,7 @@ mod tests {
             cards: vec![Card {
                 rank: Rank::Ace,
                 suit: Suit::Club,
+                deck: 0,
             }],
         };
         let mut new_stack = CardStack::new();
@@ -166,6 +198,7 @@ mod tests {
             cards: vec![Card {
                 rank: Rank::Ace,
                 suit: Suit::Club,
+                deck: 0,
             }],
         };
         let mut new_stack = CardStack::new();
@@ -198,12 +231,14 @@ mod tests {
             cards: vec![Card {
                 rank: Rank::Ace,
                 suit: Suit::Club,
+                deck: 0,
             }],
         };
         let mut new_stack = CardStack::new();
         let test_single = vec![Card {
             rank: Rank::Ace,
             suit: Suit::Club,
+            deck: 0,
         }];
 
         let test_straight_res =
@@ -232,6 +267,7 @@ mod tests {
             cards: vec![Card {
                 rank: Rank::Ace,
                 suit: Suit::Club,
+                deck: 0,
             }],
         };
         let mut new_stack = CardStack::new();
@@ -239,6 +275,7 @@ mod tests {
         let test_single = vec![Card {
             rank: Rank::Ace,
             suit: Suit::Club,
+            deck: 0,
         }];
         let test_straight_res =
             get_test_hand(&test_player, ComboType::Straight, RelativeStrength::Normal);
@@ -262,4 +299,199 @@ mod tests {
             new_stack.add(&test_single, &test_player_2).unwrap();
         }
     }
+
+    #[test]
+    fn test_pass_trick() {
+        let test_cards = vec![
+            Card {
+                rank: Rank::Ace,
+                suit: Suit::Club,
+                deck: 0,
+            },
+            Card {
+                rank: Rank::Two,
+                suit: Suit::Club,
+                deck: 0,
+            },
+        ];
+        let players = (0..3)
+            .map(|id| Player {
+                id,
+                cards: test_cards.clone(),
+            })
+            .collect_vec();
+        let mut new_stack = CardStack::new();
+
+        // Cannot pass on an empty stack.
+        assert!(new_stack.pass(&players[0]).is_err());
+        assert_eq!(new_stack.leader(), None);
+
+        new_stack.add(&test_cards[..1], &players[0]).unwrap();
+        new_stack.pass(&players[1]).unwrap();
+        assert_eq!(new_stack.passes, vec![1]);
+
+        // Playing a hand resets passes.
+        new_stack.add(&test_cards[1..], &players[2]).unwrap();
+        assert!(new_stack.passes.is_empty());
+        assert_eq!(new_stack.leader(), Some(2));
+
+        new_stack.pass(&players[0]).unwrap();
+        assert!(!new_stack.is_finished(3));
+        new_stack.pass(&players[1]).unwrap();
+        assert!(new_stack.is_finished(3));
+        assert_eq!(new_stack.leader(), Some(2));
+
+        new_stack.clear();
+        assert!(new_stack.passes.is_empty());
+        assert!(!new_stack.is_finished(3));
+    }
+
+    #[test]
+    fn test_pass_twice() {
+        let card = Card::new(Rank::Ace, Suit::Club);
+        let players = (0..4)
+            .map(|id| Player {
+                id,
+                cards: vec![card],
+            })
+            .collect_vec();
+        let mut new_stack = CardStack::new();
+        new_stack.add(&[card], &players[0]).unwrap();
+        new_stack.pass(&players[1]).unwrap();
+
+        // A repeated pass is rejected and doesn't end the trick early.
+        assert!(new_stack.pass(&players[1]).is_err());
+        assert_eq!(new_stack.passes, vec![1]);
+        assert!(!new_stack.is_finished(3));
+    }
+
+    #[test]
+    fn test_pass_leader() {
+        let card = Card::new(Rank::Ace, Suit::Club);
+        let players = (0..3)
+            .map(|id| Player {
+                id,
+                cards: vec![card],
+            })
+            .collect_vec();
+        let mut new_stack = CardStack::new();
+        new_stack.add(&[card], &players[2]).unwrap();
+
+        // The player that played the last hand can't pass on it.
+        assert!(new_stack.pass(&players[2]).is_err());
+        assert!(new_stack.passes.is_empty());
+        new_stack.pass(&players[0]).unwrap();
+        assert_eq!(new_stack.passes, vec![0]);
+    }
+
+    #[test]
+    fn test_add_identical_single() {
+        let card = Card::new(Rank::Ace, Suit::Club);
+        let identical_card = Card { deck: 1, ..card };
+        let test_player = Player {
+            id: 1,
+            cards: vec![card, identical_card],
+        };
+        let mut new_stack = CardStack::new();
+
+        // Identical card from another deck doesn't beat the previous hand.
+        new_stack.add(&[card], &test_player).unwrap();
+        assert!(new_stack.add(&[identical_card], &test_player).is_err());
+
+        // But both copies can be played as a double.
+        new_stack.clear();
+        new_stack
+            .add(&[card, identical_card], &test_player)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_add_with_rules() {
+        let ace_diamonds = Card::new(Rank::Ace, Suit::Diamond);
+        let ace_spades = Card::new(Rank::Ace, Suit::Spade);
+        let test_player = Player {
+            id: 1,
+            cards: vec![ace_diamonds, ace_spades],
+        };
+
+        // Spades are stronger than diamonds in standard rules.
+        let mut new_stack = CardStack::new();
+        new_stack.add(&[ace_diamonds], &test_player).unwrap();
+        new_stack.add(&[ace_spades], &test_player).unwrap();
+
+        // Diamonds are the strongest suit in Pusoy Dos.
+        let mut new_stack = CardStack::with_rules(RuleSet::pusoy_dos());
+        new_stack.add(&[ace_spades], &test_player).unwrap();
+        new_stack.add(&[ace_diamonds], &test_player).unwrap();
+        new_stack.clear();
+        new_stack.add(&[ace_diamonds], &test_player).unwrap();
+        assert!(new_stack.add(&[ace_spades], &test_player).is_err());
+    }
+
+    #[test]
+    fn test_add_triple() {
+        let triple_threes = [
+            Card::new(Rank::Three, Suit::Diamond),
+            Card::new(Rank::Three, Suit::Club),
+            Card::new(Rank::Three, Suit::Heart),
+        ];
+        let triple_fours = [
+            Card::new(Rank::Four, Suit::Diamond),
+            Card::new(Rank::Four, Suit::Club),
+            Card::new(Rank::Four, Suit::Heart),
+        ];
+        let test_player = Player {
+            id: 1,
+            cards: [triple_threes, triple_fours].concat(),
+        };
+
+        // Triples are not allowed in standard rules.
+        let mut new_stack = CardStack::new();
+        assert!(new_stack.add(&triple_threes, &test_player).is_err());
+
+        let mut new_stack = CardStack::with_rules(RuleSet {
+            allow_triples: true,
+            ..Default::default()
+        });
+        new_stack.add(&triple_threes, &test_player).unwrap();
+        assert_eq!(new_stack.kind, HandType::Triple);
+        assert!(new_stack.add(&triple_fours[..2], &test_player).is_err());
+        new_stack.add(&triple_fours, &test_player).unwrap();
+        assert!(new_stack.add(&triple_threes, &test_player).is_err());
+    }
+
+    #[test]
+    fn test_bomb_interrupts() {
+        let test_player = Player {
+            id: 1,
+            cards: vec![],
+        };
+        let ace = Card::new(Rank::Ace, Suit::Spade);
+        let bomb = get_test_hand(&test_player, ComboType::Bomb, RelativeStrength::Normal).unwrap();
+        let straight_flush = get_test_hand(
+            &test_player,
+            ComboType::StraightFlush,
+            RelativeStrength::Normal,
+        )
+        .unwrap();
+
+        // Bombs can't be played on singles in standard rules.
+        let mut new_stack = CardStack::new();
+        new_stack.add(&[ace], &test_player).unwrap();
+        assert!(new_stack.add(&bomb.cards, &test_player).is_err());
+
+        let mut new_stack = CardStack::with_rules(RuleSet {
+            bomb_interrupts: true,
+            ..Default::default()
+        });
+        new_stack.add(&[ace], &test_player).unwrap();
+        new_stack.add(&bomb.cards, &test_player).unwrap();
+        assert_eq!(new_stack.kind, HandType::Combo);
+        assert_eq!(new_stack.combo, ComboType::Bomb);
+
+        // Only a stronger bomb can follow.
+        assert!(new_stack.add(&[ace], &test_player).is_err());
+        new_stack.add(&straight_flush.cards, &test_player).unwrap();
+        assert!(new_stack.add(&bomb.cards, &test_player).is_err());
+    }
 }
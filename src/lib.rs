@@ -0,0 +1,46 @@
+//! [Big Two](https://en.wikipedia.org/wiki/Big_two) card game engine.
+//!
+//! The crate is split into two modules:
+//! - [`common`]: The card model. `Card`s, `Deck`s, `Player`s, `Hand` validation and the `CardStack`.
+//! - [`logic`]: Combo enumeration, computer move selection and the `Game` engine.
+//!
+//! The most commonly used types are re-exported at the crate root.
+//!
+//! ```
+//! use big2::{Card, Hand, Player, Rank, Suit};
+//!
+//! let card = Card::new(Rank::Three, Suit::Diamond);
+//! let player = Player { id: 0, cards: vec![card] };
+//!
+//! let hand = Hand::new(&[card], &player).unwrap();
+//! assert_eq!(hand.kind, big2::HandType::Single);
+//! ```
+
+pub mod common;
+pub mod logic;
+
+pub use common::{
+    card::Card,
+    deck::Deck,
+    error::{DeckError, GameError, HandError},
+    hand::{ComboType, Hand, HandType},
+    player::Player,
+    rank::Rank,
+    rules::{FlushRanking, RuleSet, StraightRanking, StraightRules, Variant},
+    stack::CardStack,
+    suit::Suit,
+};
+pub use logic::{
+    agent::{
+        Agent, Command, HeuristicAgent, RandomAgent, ScriptedAgent, Seat, TerminalAgent, Turn,
+    },
+    choice::{choose_move, choose_move_with},
+    combo::get_combos,
+    config::{GameConfig, Leftover, MatchConfig, NextLead, UndoPolicy},
+    event::GameEvent,
+    game::{Action, Game, Placement, STARTING_CARD},
+    moves::{legal_moves, legal_moves_in},
+    score::{Match, Scoreboard, ScoringRules},
+    special::{SpecialAward, SpecialHand, SpecialHandRules},
+    view::PlayerView,
+};
@@ -0,0 +1,356 @@
+use crate::common::{card::Card, hand::HandType};
+use crate::logic::{
+    choice::choose_move_with, game::Action, moves::legal_moves_in, view::PlayerView,
+};
+use itertools::Itertools;
+use rand::{seq::SliceRandom, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use regex::Regex;
+use std::collections::VecDeque;
+use std::io;
+
+/// Keys a player at the terminal can enter on their turn.
+pub const TERMINAL_KEYS: &str =
+    "- q : Quit\n- p : Pass\n- h : Print this message.\n- s : Sort hand.\n- r : Restart.\n- u : Undo.\n- y : Redo.\n-c : Player a computer move.\n";
+
+/// Request from a seat to the game loop rather than a move in the game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    /// Stop playing.
+    Quit,
+    /// Deal new cards.
+    Restart,
+    /// Take back the last move.
+    Undo,
+    /// Play the last move taken back again.
+    Redo,
+}
+
+/// What a player at the terminal enters on their turn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Turn {
+    /// Take an action in the game.
+    Action(Action),
+    /// Give the game loop a command instead of taking an action.
+    Command(Command),
+}
+
+/// Controller for a seat. Chooses moves from what the seat is allowed to see.
+pub trait Agent {
+    /// Choose the action for the viewing player's turn.
+    fn choose(&mut self, view: &PlayerView) -> Action;
+}
+
+/// Who plays a seat in the game loop.
+pub enum Seat {
+    /// Person at the terminal, who can also give the game loop commands.
+    Terminal(TerminalAgent),
+    /// Any other controller, whose moves are announced once played.
+    Agent(Box<dyn Agent>),
+}
+
+impl Seat {
+    /// Ask the seat what to do on the viewing player's turn.
+    pub fn turn(&mut self, view: &PlayerView) -> Turn {
+        match self {
+            Seat::Terminal(agent) => agent.read_turn(view),
+            Seat::Agent(agent) => Turn::Action(agent.choose(view)),
+        }
+    }
+
+    /// Whether the seat is played by a person.
+    pub fn is_human(&self) -> bool {
+        matches!(self, Seat::Terminal(_))
+    }
+}
+
+/// Computer player using the built-in heuristic in [`choose_move_with`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HeuristicAgent;
+
+impl Agent for HeuristicAgent {
+    fn choose(&mut self, view: &PlayerView) -> Action {
+        match choose_move_with(
+            &view.cards,
+            &view.player(),
+            view.top(),
+            view.seat,
+            &view.n_cards_left,
+            &view.rules,
+            view.required_card,
+        ) {
+            Some((hand, _)) => Action::Play(hand.cards),
+            None => Action::Pass,
+        }
+    }
+}
+
+/// Computer player that picks any legal move at random.
+#[derive(Debug, Clone)]
+pub struct RandomAgent {
+    rng: ChaCha8Rng,
+}
+
+impl RandomAgent {
+    /// Create a random player. The same `seed` picks the same moves.
+    pub fn new(seed: u64) -> RandomAgent {
+        RandomAgent {
+            rng: ChaCha8Rng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Agent for RandomAgent {
+    fn choose(&mut self, view: &PlayerView) -> Action {
+        legal_moves_in(view)
+            .choose(&mut self.rng)
+            .cloned()
+            .unwrap_or(Action::Pass)
+    }
+}
+
+/// Player that takes a fixed list of actions in order. Passes once the script runs out.
+#[derive(Debug, Clone, Default)]
+pub struct ScriptedAgent {
+    /// Actions left to take, next first.
+    pub actions: VecDeque<Action>,
+}
+
+impl ScriptedAgent {
+    /// Create a player that takes the `actions` in order.
+    pub fn new(actions: Vec<Action>) -> ScriptedAgent {
+        ScriptedAgent {
+            actions: actions.into(),
+        }
+    }
+}
+
+impl Agent for ScriptedAgent {
+    fn choose(&mut self, _view: &PlayerView) -> Action {
+        self.actions.pop_front().unwrap_or(Action::Pass)
+    }
+}
+
+/// Person playing at the terminal. Prints their view and reads card indices from stdin.
+#[derive(Debug, Clone, Default)]
+pub struct TerminalAgent {
+    /// Show the hand sorted by card value.
+    pub sorted: bool,
+}
+
+impl TerminalAgent {
+    /// Cards in the order they are shown to the player.
+    fn shown_cards(&self, view: &PlayerView) -> Vec<Card> {
+        if self.sorted {
+            view.cards
+                .iter()
+                .copied()
+                .sorted_by(|card_1, card_2| view.rules.cmp_cards(card_1, card_2))
+                .collect_vec()
+        } else {
+            view.cards.clone()
+        }
+    }
+
+    /// Print the state of the game and the player's hand.
+    fn print(&self, view: &PlayerView) {
+        println!("Current Turn: {}", view.turn);
+        if let Some(prev_hand) = view.top() {
+            println!(
+                "Current Hand: {:?} (Player {})",
+                prev_hand.cards,
+                prev_hand.player + 1
+            );
+        } else {
+            println!("Current Hand: None");
+        }
+
+        // Format current mode string if combo.
+        let curr_mode = match view.top() {
+            Some(prev_hand) if prev_hand.kind == HandType::Combo => {
+                format!("{:?} ({:?})", prev_hand.kind, prev_hand.combo)
+            }
+            Some(prev_hand) => format!("{:?}", prev_hand.kind),
+            None => format!("{:?}", HandType::None),
+        };
+
+        println!("Current Mode: {:?}\n", curr_mode);
+        println!("Your Hand (Player {}):", view.seat + 1);
+
+        for (idx, card) in self.shown_cards(view).iter().enumerate() {
+            println!("{} - {:?}", idx, card)
+        }
+    }
+
+    /// Read what the player enters on their turn: an action or a command for the game loop.
+    ///
+    /// A closed stdin is treated as quitting.
+    pub fn read_turn(&mut self, view: &PlayerView) -> Turn {
+        self.read(view).unwrap_or(Turn::Command(Command::Quit))
+    }
+
+    /// Read the player's turn, or `None` once stdin is closed.
+    fn read(&mut self, view: &PlayerView) -> Option<Turn> {
+        let card_idx_pattern = Regex::new(r"(\d+,*)+").unwrap();
+        let mut user_input = String::new();
+
+        loop {
+            self.print(view);
+
+            // First clear the String. Otherwise it will keep adding to it
+            user_input.clear();
+            if io::stdin().read_line(&mut user_input).unwrap_or(0) == 0 {
+                return None;
+            }
+
+            // Match user input.
+            return Some(match user_input.trim() {
+                "c" => Turn::Action(HeuristicAgent.choose(view)),
+                "p" => Turn::Action(Action::Pass),
+                "q" => Turn::Command(Command::Quit),
+                "r" => Turn::Command(Command::Restart),
+                "u" => Turn::Command(Command::Undo),
+                "y" => Turn::Command(Command::Redo),
+                "h" => {
+                    println!("{TERMINAL_KEYS}");
+                    continue;
+                }
+                "s" => {
+                    self.sorted = true;
+                    continue;
+                }
+                input => {
+                    // Search user input for pattern.
+                    if !card_idx_pattern.is_match(input) {
+                        println!(
+                            "Invalid indices ({}). Doesn't match pattern: \\d,\\d,...\n",
+                            input
+                        );
+                        continue;
+                    }
+
+                    let mut card_idx: Vec<usize> = vec![];
+                    for idx in input.split(',') {
+                        if let Ok(parsed_idx) = idx.parse::<usize>() {
+                            card_idx.push(parsed_idx)
+                        } else {
+                            println!("Invalid index. ({idx})\n");
+                        }
+                    }
+
+                    if card_idx.is_empty() {
+                        continue;
+                    }
+
+                    let shown_cards = self.shown_cards(view);
+                    let mut playing_hand = vec![];
+                    for idx in card_idx.iter().sorted() {
+                        if let Some(card) = shown_cards.get(*idx) {
+                            playing_hand.push(*card)
+                        } else {
+                            println!("Index of ({idx}) is not in hand.")
+                        }
+                    }
+
+                    Turn::Action(Action::Play(playing_hand))
+                }
+            });
+        }
+    }
+}
+
+/// Without a game loop to run them, commands are refused. Passes once stdin is closed.
+impl Agent for TerminalAgent {
+    fn choose(&mut self, view: &PlayerView) -> Action {
+        loop {
+            match self.read(view) {
+                Some(Turn::Action(action)) => return action,
+                Some(Turn::Command(command)) => println!("{command:?} is not available here.\n"),
+                None => return Action::Pass,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Agent, HeuristicAgent, RandomAgent, ScriptedAgent, Seat, TerminalAgent};
+    use crate::common::{card::Card, deck::Deck, player::Player, rank::Rank, suit::Suit};
+    use crate::logic::{
+        config::GameConfig,
+        game::{Action, Game, STARTING_CARD},
+        moves::legal_moves,
+    };
+
+    fn get_test_game() -> Game {
+        let players = vec![
+            Player {
+                id: 0,
+                cards: vec![STARTING_CARD, Card::new(Rank::Four, Suit::Club)],
+            },
+            Player {
+                id: 1,
+                cards: vec![
+                    Card::new(Rank::Ace, Suit::Heart),
+                    Card::new(Rank::Five, Suit::Spade),
+                ],
+            },
+        ];
+        Game::from_deal(GameConfig::default(), Deck::new(false).unwrap(), players).unwrap()
+    }
+
+    #[test]
+    fn test_agents_play_game() {
+        let mut game = get_test_game();
+        let mut agents: Vec<Box<dyn Agent>> = vec![
+            Box::new(ScriptedAgent::new(vec![Action::Play(vec![STARTING_CARD])])),
+            Box::new(RandomAgent::new(0)),
+        ];
+
+        let view = game.view(0).unwrap();
+        let action = agents[0].choose(&view);
+        game.apply(action).unwrap();
+
+        // Random moves are always legal.
+        let view = game.view(1).unwrap();
+        let action = agents[1].choose(&view);
+        assert!(legal_moves(&game, 1).contains(&action));
+        game.apply(action).unwrap();
+
+        // Script ran out.
+        assert_eq!(agents[0].choose(&view), Action::Pass);
+    }
+
+    #[test]
+    fn test_seat_is_human() {
+        assert!(Seat::Terminal(TerminalAgent::default()).is_human());
+        assert!(!Seat::Agent(Box::new(HeuristicAgent)).is_human());
+    }
+
+    #[test]
+    fn test_heuristic_agent() {
+        let game = get_test_game();
+        let view = game.view(0).unwrap();
+
+        // Must open with the 3 of diamonds.
+        assert_eq!(
+            HeuristicAgent.choose(&view),
+            Action::Play(vec![STARTING_CARD])
+        );
+    }
+
+    #[test]
+    fn test_random_agent_seed() {
+        let mut game = get_test_game();
+        game.apply(Action::Play(vec![STARTING_CARD])).unwrap();
+        let view = game.view(1).unwrap();
+
+        let choices = |seed| {
+            let mut agent = RandomAgent::new(seed);
+            (0..10)
+                .map(|_| agent.choose(&view))
+                .collect::<Vec<Action>>()
+        };
+        assert_eq!(choices(7), choices(7));
+    }
+}
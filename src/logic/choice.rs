@@ -2,23 +2,33 @@ use crate::common::{
     card::Card,
     hand::{ComboType, Gauge, Hand, HandType},
     player::Player,
+    rank::Rank,
+    rules::{RuleSet, Variant},
+};
+use crate::logic::{
+    combo::{get_combos_with, get_dupes},
+    moves::get_hands,
 };
-use crate::logic::combo::{get_combos, get_dupes};
 use itertools::Itertools;
+use std::cmp::Ordering::Greater;
+use std::collections::HashMap;
 
-pub fn get_sorted_hands<'a>(hands: &'a [Vec<Card>], player: &Player) -> Vec<(&'a Vec<Card>, f32)> {
+/// Sort valid `hands` from weakest to strongest, pairing each with its strength.
+pub fn get_sorted_hands<'a>(
+    hands: &'a [Vec<Card>],
+    player: &Player,
+    rules: &RuleSet,
+) -> Vec<(&'a Vec<Card>, u64)> {
     hands
         .iter()
         .filter_map(|hand| {
-            if let Ok(hand_strength) =
-                Hand::new(hand, player).and_then(|hand| Ok(hand.strength().unwrap()))
-            {
-                Some((hand, hand_strength))
-            } else {
-                None
-            }
+            let hand_strength = Hand::with_rules(hand, player, rules)
+                .ok()?
+                .strength_with(rules)
+                .ok()?;
+            Some((hand, hand_strength))
         })
-        .sorted_by(|(_, dupe_1_val), (_, dupe_2_val)| dupe_1_val.partial_cmp(dupe_2_val).unwrap())
+        .sorted_by_key(|(_, hand_strength)| *hand_strength)
         .collect_vec()
 }
 
@@ -28,17 +38,18 @@ fn filter_cards_by_strength(
     prev_hand: Option<&Hand>,
     omit_cards: &[&Card],
     opponent_close_to_win: bool,
+    rules: &RuleSet,
 ) -> Option<Hand> {
     let possible_hands = cards
         .iter()
         .filter_map(|cards| {
-            let hand = Hand::new(cards, player).unwrap();
+            let hand = Hand::with_rules(cards, player, rules).ok()?;
 
             let contains_cards_to_omit = cards.iter().any(|card| omit_cards.contains(&card));
 
             // If previous hand exists, compare hands. Otherwise new hand is always stronger.
             let is_stronger_hand = if let Some(prev_hand) = prev_hand {
-                hand > *prev_hand
+                hand.partial_cmp_with(prev_hand, rules) == Some(Greater)
             } else {
                 true
             };
@@ -49,7 +60,7 @@ fn filter_cards_by_strength(
                 None
             }
         })
-        .sorted()
+        .sorted_by_key(|hand| hand.strength_with(rules).ok())
         .collect_vec();
 
     // Play strongest cards if any player is close to winning.
@@ -61,6 +72,9 @@ fn filter_cards_by_strength(
     .cloned()
 }
 
+/// Choose a hand for a computer `player` to play on top of `prev_hand`.
+///
+/// Returns `None` if the player should pass.
 // Devalue sequential doubles
 // Reduce total number of moves
 // Use weakest cards in combo if possible.
@@ -72,17 +86,70 @@ pub fn choose_move<'a>(
     prev_hand: Option<&Hand>,
     current_pos: usize,
     n_cards_left: &[usize],
+    rules: &RuleSet,
 ) -> Option<(Hand, &'a Player)> {
-    // If any player is under 4 cards.
+    choose_move_with(
+        cards,
+        player,
+        prev_hand,
+        current_pos,
+        n_cards_left,
+        rules,
+        None,
+    )
+}
+
+/// Choose a hand for a computer `player` that must include the `required_card`, if any.
+///
+/// Use [`Game::required_card`](crate::logic::game::Game::required_card) to open a game.
+pub fn choose_move_with<'a>(
+    cards: &[Card],
+    player: &'a Player,
+    prev_hand: Option<&Hand>,
+    current_pos: usize,
+    n_cards_left: &[usize],
+    rules: &RuleSet,
+    required_card: Option<Card>,
+) -> Option<(Hand, &'a Player)> {
+    // If any player still in the game is under 4 cards.
     let opponent_close_to_win = n_cards_left
         .iter()
         .enumerate()
-        .any(|(i, n_cards)| *n_cards <= 4 && i != current_pos);
+        .any(|(i, n_cards)| (1..=4).contains(n_cards) && i != current_pos);
+
+    // Lead the highest single if the next player still in the game has one card left.
+    let n_cards_next = (1..n_cards_left.len())
+        .map(|offset| n_cards_left[(current_pos + offset) % n_cards_left.len()])
+        .find(|n_cards| *n_cards > 0)
+        .unwrap_or(0);
+    let last_card_single = rules
+        .last_card_single(cards, prev_hand.is_none(), n_cards_next)
+        .filter(|_| required_card.is_none());
+    let obey_last_card = |hand_to_play: Hand| match last_card_single {
+        Some(highest_card) if hand_to_play.kind == HandType::Single => {
+            Hand::with_rules(&[highest_card], player, rules).unwrap_or(hand_to_play)
+        }
+        _ => hand_to_play,
+    };
+
+    if rules.variant == Variant::TienLen {
+        return choose_tien_len_move(
+            cards,
+            player,
+            prev_hand,
+            opponent_close_to_win,
+            rules,
+            required_card,
+        )
+        .map(|hand_to_play| (obey_last_card(hand_to_play), player));
+    }
 
     let mut strongest_hands: Vec<Vec<Card>> = vec![];
 
     let dupe_combos = get_dupes(cards, 2);
-    let five_card_combos = get_combos(cards);
+    let triple_combos = get_dupes(cards, 3).filter(|_| rules.allows(HandType::Triple));
+    let quad_combos = get_dupes(cards, 4).filter(|_| rules.allows(HandType::Quad));
+    let five_card_combos = get_combos_with(cards, rules);
 
     // println!("Hand: {:?}", cards);
     // println!("Duplicates: {:?}", dupe_combos);
@@ -92,7 +159,7 @@ pub fn choose_move<'a>(
     // If any player close to winning, don't filter out strongest cards.
     let strongest_hand_cards = if !opponent_close_to_win {
         if let Some(dupes) = &dupe_combos {
-            let largest_dupes = get_sorted_hands(dupes, player);
+            let largest_dupes = get_sorted_hands(dupes, player, rules);
             // Consider all duplicates.
             for dupe in largest_dupes {
                 strongest_hands.push(dupe.0.to_vec())
@@ -100,8 +167,8 @@ pub fn choose_move<'a>(
         }
 
         if let Some(combos) = &five_card_combos {
-            for (_, possible_combos) in combos.iter() {
-                let sorted_combos = get_sorted_hands(possible_combos, player);
+            for possible_combos in combos_in_order(combos) {
+                let sorted_combos = get_sorted_hands(possible_combos, player, rules);
                 // Only consider largest combo.
                 let max_combo = sorted_combos.last().unwrap().0;
                 strongest_hands.push(max_combo.to_vec())
@@ -111,7 +178,7 @@ pub fn choose_move<'a>(
         strongest_hands
             .iter()
             .flatten()
-            .sorted()
+            .sorted_by(|card_1, card_2| rules.cmp_cards(card_1, card_2))
             .dedup()
             .collect_vec()
     } else {
@@ -128,6 +195,7 @@ pub fn choose_move<'a>(
                 prev_hand,
                 &strongest_hand_cards,
                 opponent_close_to_win,
+                rules,
             )
         }
         HandType::Double => {
@@ -138,6 +206,27 @@ pub fn choose_move<'a>(
                     prev_hand,
                     &strongest_hand_cards,
                     opponent_close_to_win,
+                    rules,
+                )
+            } else {
+                None
+            }
+        }
+        HandType::Triple | HandType::Quad => {
+            let same_rank_hands = if prev_hand_kind == HandType::Triple {
+                &triple_combos
+            } else {
+                &quad_combos
+            };
+            if let Some(same_rank_hands) = same_rank_hands {
+                // Triples and quads are rare. Don't hold back the strongest cards.
+                filter_cards_by_strength(
+                    same_rank_hands,
+                    player,
+                    prev_hand,
+                    &[],
+                    opponent_close_to_win,
+                    rules,
                 )
             } else {
                 None
@@ -147,36 +236,46 @@ pub fn choose_move<'a>(
             if let Some(five_card_hands) = &five_card_combos {
                 // No cards omitted from hand.
                 // Just use lowest combo found.
-                five_card_hands
-                    .iter()
-                    .filter_map(|(_, cards)| {
+                combos_in_order(five_card_hands)
+                    .filter_map(|cards| {
                         filter_cards_by_strength(
                             cards,
                             player,
                             prev_hand,
                             &[],
                             opponent_close_to_win,
+                            rules,
                         )
                     })
                     // Use lowest hand possible to beat hand.
-                    .min_by(|combo_1, combo_2| combo_1.cmp(combo_2))
+                    .min_by_key(|combo| combo.strength_with(rules).ok())
             } else {
                 None
             }
         }
         _ => {
-            // Use five card hands first, then doubles and then singles.
-            let possible_hands = if let Some(five_cards) = five_card_combos {
-                five_cards
+            // Use five card hands first, then quads, triples, doubles and then singles.
+            // Only consider hands with the required card, if any.
+            let singles = cards.iter().map(|card| vec![*card]).collect_vec();
+            let possible_hands = [
+                five_card_combos
+                    .as_ref()
+                    .map(|five_cards| combos_in_order(five_cards).flatten().cloned().collect_vec()),
+                quad_combos.clone(),
+                triple_combos,
+                dupe_combos,
+                Some(singles),
+            ]
+            .into_iter()
+            .flatten()
+            .map(|hands| {
+                hands
                     .into_iter()
-                    .map(|(_, cards)| cards)
-                    .flatten()
+                    .filter(|hand| required_card.is_none_or(|card| hand.contains(&card)))
                     .collect_vec()
-            } else if let Some(doubles) = dupe_combos {
-                doubles
-            } else {
-                cards.iter().map(|card| vec![*card]).collect_vec()
-            };
+            })
+            .find(|hands| !hands.is_empty())
+            .unwrap_or_default();
 
             // On new stack, play hand with no cards filtered out.
             filter_cards_by_strength(
@@ -185,24 +284,118 @@ pub fn choose_move<'a>(
                 prev_hand,
                 &[],
                 opponent_close_to_win,
+                rules,
             )
         }
     };
 
-    possible_hand_to_play.map(|hand_to_play| (hand_to_play, player))
+    // Spend the weakest bomb to interrupt the trick if an opponent is close to winning.
+    let possible_hand_to_play = match (possible_hand_to_play, prev_hand) {
+        (None, Some(prev_hand)) if rules.bomb_interrupts && opponent_close_to_win => choose_bomb(
+            five_card_combos.as_ref(),
+            quad_combos.as_ref(),
+            player,
+            prev_hand,
+            rules,
+        ),
+        (hand_to_play, _) => hand_to_play,
+    };
+
+    possible_hand_to_play.map(|hand_to_play| (obey_last_card(hand_to_play), player))
+}
+
+/// Choose a hand to play in Tiến lên.
+///
+/// Leads with the longest hand containing the required card, or the weakest card, to shed cards quickly.
+/// Responds with the weakest hand of the same kind and only chops when a 2 was played or an opponent is close to winning.
+fn choose_tien_len_move(
+    cards: &[Card],
+    player: &Player,
+    prev_hand: Option<&Hand>,
+    opponent_close_to_win: bool,
+    rules: &RuleSet,
+    required_card: Option<Card>,
+) -> Option<Hand> {
+    let hands = get_hands(cards, rules)
+        .into_iter()
+        .filter_map(|hand| Hand::with_rules(&hand, player, rules).ok())
+        .filter(|hand| rules.allows(hand.kind));
+
+    let Some(prev_hand) = prev_hand else {
+        let lead_card = required_card.or_else(|| {
+            cards
+                .iter()
+                .min_by(|card_1, card_2| rules.cmp_cards(card_1, card_2))
+                .copied()
+        })?;
+        return hands
+            .filter(|hand| hand.cards.contains(&lead_card))
+            .max_by_key(|hand| hand.cards.len());
+    };
+
+    let (same_kind, chops): (Vec<Hand>, Vec<Hand>) = hands
+        .filter(|hand| rules.beats(hand, prev_hand).is_ok())
+        .partition(|hand| hand.kind == prev_hand.kind);
+
+    let strength = |hand: &Hand| hand.strength_with(rules).ok();
+    let prev_has_twos = prev_hand.cards.iter().any(|card| card.rank == Rank::Two);
+
+    if opponent_close_to_win {
+        same_kind.into_iter().max_by_key(strength)
+    } else {
+        same_kind.into_iter().min_by_key(strength)
+    }
+    .or_else(|| {
+        (prev_has_twos || opponent_close_to_win)
+            .then(|| chops.into_iter().min_by_key(|hand| hand.cards.len()))
+            .flatten()
+    })
+}
+
+/// Hands of each combo type from weakest to strongest type.
+///
+/// Iterating in a fixed order means the same cards always give the same move.
+fn combos_in_order(
+    combos: &HashMap<ComboType, Vec<Vec<Card>>>,
+) -> impl Iterator<Item = &Vec<Vec<Card>>> {
+    combos
+        .iter()
+        .sorted_by_key(|(combo_type, _)| **combo_type as usize)
+        .map(|(_, hands)| hands)
+}
+
+/// Choose the weakest bomb that beats `prev_hand`.
+fn choose_bomb(
+    five_card_combos: Option<&HashMap<ComboType, Vec<Vec<Card>>>>,
+    quad_combos: Option<&Vec<Vec<Card>>>,
+    player: &Player,
+    prev_hand: &Hand,
+    rules: &RuleSet,
+) -> Option<Hand> {
+    let prev_bomb_strength = prev_hand.bomb_strength_with(rules);
+
+    five_card_combos
+        .into_iter()
+        .flat_map(|combos| combos_in_order(combos).flatten())
+        .chain(quad_combos.into_iter().flatten())
+        .filter_map(|cards| Hand::with_rules(cards, player, rules).ok())
+        .filter_map(|hand| {
+            let bomb_strength = hand.bomb_strength_with(rules)?;
+            (Some(bomb_strength) > prev_bomb_strength).then_some((hand, bomb_strength))
+        })
+        .min_by_key(|(_, bomb_strength)| *bomb_strength)
+        .map(|(hand, _)| hand)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::choose_move;
+    use super::{choose_move, choose_move_with};
     use crate::common::{
         card::Card,
-        hand::{
-            tests::{get_test_hand, RelativeStrength},
-            ComboType, Hand,
-        },
+        hand::{Hand, HandType},
         player::Player,
         rank::Rank,
+        rules::RuleSet,
         suit::Suit,
     };
 
@@ -216,17 +409,23 @@ mod tests {
             cards: cards.clone(),
         };
         let hand_single = Hand::new(
-            &vec![Card {
+            &[Card {
                 rank: Rank::Three,
                 suit: Suit::Diamond,
+                deck: 0,
             }],
             &test_player,
         )
         .unwrap();
 
-        if let Some(chosen_single) =
-            choose_move(&cards, &test_player, Some(&hand_single), 0, &[12, 12])
-        {
+        if let Some(chosen_single) = choose_move(
+            &cards,
+            &test_player,
+            Some(&hand_single),
+            0,
+            &[12, 12],
+            &RuleSet::default(),
+        ) {
             println!("{:?}", chosen_single)
         }
     }
@@ -241,23 +440,30 @@ mod tests {
             cards: cards.clone(),
         };
         let hand_double = Hand::new(
-            &vec![
+            &[
                 Card {
                     rank: Rank::Three,
                     suit: Suit::Diamond,
+                    deck: 0,
                 },
                 Card {
                     rank: Rank::Three,
                     suit: Suit::Club,
+                    deck: 0,
                 },
             ],
             &test_player,
         )
         .unwrap();
 
-        if let Some(chosen_double) =
-            choose_move(&cards, &test_player, Some(&hand_double), 0, &[12, 12])
-        {
+        if let Some(chosen_double) = choose_move(
+            &cards,
+            &test_player,
+            Some(&hand_double),
+            0,
+            &[12, 12],
+            &RuleSet::default(),
+        ) {
             println!("{:?}", chosen_double)
         }
     }
@@ -272,39 +478,228 @@ mod tests {
             cards: cards.clone(),
         };
         let hand_straight = Hand::new(
-            &vec![
+            &[
                 Card {
                     rank: Rank::Three,
                     suit: Suit::Diamond,
+                    deck: 0,
                 },
                 Card {
                     rank: Rank::Four,
                     suit: Suit::Club,
+                    deck: 0,
                 },
                 Card {
                     rank: Rank::Five,
                     suit: Suit::Diamond,
+                    deck: 0,
                 },
                 Card {
                     rank: Rank::Six,
                     suit: Suit::Club,
+                    deck: 0,
                 },
                 Card {
                     rank: Rank::Seven,
                     suit: Suit::Diamond,
+                    deck: 0,
                 },
             ],
             &test_player,
         )
         .unwrap();
 
-        if let Some(chosen_combo) =
-            choose_move(&cards, &test_player, Some(&hand_straight), 0, &[12, 12])
-        {
+        if let Some(chosen_combo) = choose_move(
+            &cards,
+            &test_player,
+            Some(&hand_straight),
+            0,
+            &[12, 12],
+            &RuleSet::default(),
+        ) {
             println!("{:?}", chosen_combo)
         }
     }
 
+    #[test]
+    fn test_respond_triple() {
+        let test_seq_file = "test/cards_dupes.json";
+        let cards: Vec<Card> =
+            serde_json::from_reader(&std::fs::File::open(test_seq_file).unwrap()).unwrap();
+        let test_player = Player {
+            id: 1,
+            cards: cards.clone(),
+        };
+        let hand_triple = Hand::new(
+            &[
+                Card::new(Rank::Four, Suit::Diamond),
+                Card::new(Rank::Four, Suit::Club),
+                Card::new(Rank::Four, Suit::Heart),
+            ],
+            &test_player,
+        )
+        .unwrap();
+        let rules = RuleSet {
+            allow_triples: true,
+            ..Default::default()
+        };
+
+        let (chosen_triple, _) = choose_move(
+            &cards,
+            &test_player,
+            Some(&hand_triple),
+            0,
+            &[12, 12],
+            &rules,
+        )
+        .unwrap();
+        assert_eq!(chosen_triple.kind, HandType::Triple);
+        assert!(chosen_triple
+            .cards
+            .iter()
+            .all(|card| card.rank == Rank::Five));
+    }
+
+    #[test]
+    fn test_respond_with_bomb() {
+        let test_seq_file = "test/cards_dupes.json";
+        let cards: Vec<Card> =
+            serde_json::from_reader(&std::fs::File::open(test_seq_file).unwrap()).unwrap();
+        let test_player = Player {
+            id: 1,
+            cards: cards.clone(),
+        };
+        let hand_single = Hand::new(&[Card::new(Rank::Two, Suit::Spade)], &test_player).unwrap();
+        let rules = RuleSet {
+            bomb_interrupts: true,
+            ..Default::default()
+        };
+
+        // Bomb is saved if no opponent is close to winning.
+        let chosen = choose_move(
+            &cards,
+            &test_player,
+            Some(&hand_single),
+            0,
+            &[12, 12],
+            &rules,
+        );
+        assert!(chosen.is_none());
+
+        let (chosen_bomb, _) = choose_move(
+            &cards,
+            &test_player,
+            Some(&hand_single),
+            0,
+            &[12, 1],
+            &rules,
+        )
+        .unwrap();
+        assert!(chosen_bomb.is_bomb());
+    }
+
+    #[test]
+    fn test_tien_len_move() {
+        let rules = RuleSet::tien_len();
+        let pairs = [Rank::Four, Rank::Five, Rank::Six]
+            .into_iter()
+            .flat_map(|rank| [Card::new(rank, Suit::Spade), Card::new(rank, Suit::Heart)])
+            .collect::<Vec<Card>>();
+        let cards = [
+            vec![
+                Card::new(Rank::Three, Suit::Club),
+                Card::new(Rank::Seven, Suit::Club),
+            ],
+            pairs.clone(),
+        ]
+        .concat();
+        let test_player = Player {
+            id: 1,
+            cards: cards.clone(),
+        };
+
+        // Lead with the longest sequence containing the weakest card.
+        let (lead, _) = choose_move(&cards, &test_player, None, 0, &[13, 13], &rules).unwrap();
+        assert_eq!(lead.kind, HandType::Sequence);
+        assert_eq!(lead.cards.len(), 5);
+
+        // Respond to a single with the weakest single.
+        let single =
+            Hand::with_rules(&[Card::new(Rank::Three, Suit::Heart)], &test_player, &rules).unwrap();
+        let (response, _) =
+            choose_move(&cards, &test_player, Some(&single), 0, &[13, 13], &rules).unwrap();
+        assert_eq!(response.cards, vec![Card::new(Rank::Four, Suit::Spade)]);
+
+        // Chop a 2 with three pairs in sequence.
+        let two =
+            Hand::with_rules(&[Card::new(Rank::Two, Suit::Heart)], &test_player, &rules).unwrap();
+        let (chop, _) =
+            choose_move(&cards, &test_player, Some(&two), 0, &[13, 13], &rules).unwrap();
+        assert_eq!(chop.kind, HandType::DoubleSequence);
+    }
+
+    #[test]
+    fn test_open_required_card() {
+        let three_diamonds = Card::new(Rank::Three, Suit::Diamond);
+        let cards = vec![
+            three_diamonds,
+            Card::new(Rank::Three, Suit::Spade),
+            Card::new(Rank::Five, Suit::Club),
+            Card::new(Rank::Six, Suit::Spade),
+            Card::new(Rank::Seven, Suit::Heart),
+            Card::new(Rank::Eight, Suit::Club),
+            Card::new(Rank::Nine, Suit::Diamond),
+            Card::new(Rank::Ten, Suit::Spade),
+        ];
+        let test_player = Player {
+            id: 0,
+            cards: cards.clone(),
+        };
+        let rules = RuleSet::default();
+
+        // Combos are led first.
+        let (lead, _) = choose_move(&cards, &test_player, None, 0, &[8, 13], &rules).unwrap();
+        assert_eq!(lead.kind, HandType::Combo);
+
+        // Only hands with the required card can open the game.
+        let (lead, _) = choose_move_with(
+            &cards,
+            &test_player,
+            None,
+            0,
+            &[8, 13],
+            &rules,
+            Some(three_diamonds),
+        )
+        .unwrap();
+        assert_eq!(lead.kind, HandType::Double);
+        assert!(lead.cards.contains(&three_diamonds));
+    }
+
+    #[test]
+    fn test_lead_last_card() {
+        let cards = vec![
+            Card::new(Rank::Four, Suit::Club),
+            Card::new(Rank::Nine, Suit::Heart),
+            Card::new(Rank::King, Suit::Spade),
+        ];
+        let test_player = Player {
+            id: 0,
+            cards: cards.clone(),
+        };
+        let rules = RuleSet {
+            last_card: true,
+            ..RuleSet::big_two()
+        };
+
+        let (lead, _) = choose_move(&cards, &test_player, None, 0, &[3, 5], &rules).unwrap();
+        assert_eq!(lead.cards, vec![Card::new(Rank::Four, Suit::Club)]);
+
+        // Next player has one card left. Lead the highest single.
+        let (lead, _) = choose_move(&cards, &test_player, None, 0, &[3, 1], &rules).unwrap();
+        assert_eq!(lead.cards, vec![Card::new(Rank::King, Suit::Spade)]);
+    }
+
     #[test]
     fn test_respond_single_start_game() {}
 
@@ -1,4 +1,10 @@
-use crate::common::{card::Card, hand::ComboType, rank::Rank, suit::Suit};
+use crate::common::{
+    card::Card,
+    hand::ComboType,
+    rank::Rank,
+    rules::{RuleSet, StraightRules},
+    suit::Suit,
+};
 use itertools::Itertools;
 use std::collections::HashMap;
 use std::thread::{self, JoinHandle};
@@ -10,11 +16,12 @@ type PossibleCombos = Option<Vec<Vec<Card>>>;
 /// Get all duplicate `Card` instances in a `hand` of some `size`.
 ///
 /// ```
-/// use crate::common::{card::Card, rank::Rank, suit::Suit};
+/// use big2::common::{card::Card, rank::Rank, suit::Suit};
+/// use big2::logic::combo::get_dupes;
 ///
-/// let card_1 = Card { rank: Rank::Ace, suit: Suit::Club };
-/// let card_2 = Card { rank: Rank::Ace, suit: Suit::Spade };
-/// let card_3 = Card { rank: Rank::Jack, suit: Suit::Club };
+/// let card_1 = Card::new(Rank::Ace, Suit::Club);
+/// let card_2 = Card::new(Rank::Ace, Suit::Spade);
+/// let card_3 = Card::new(Rank::Jack, Suit::Club);
 ///
 /// let hand = vec![card_1, card_2, card_3];
 /// if let Some(doubles) = get_dupes(&hand, 2) {
@@ -65,6 +72,7 @@ pub fn get_dupes(hand: &[Card], size: usize) -> PossibleCombos {
     }
 }
 
+/// Get all bombs (four of a kind plus a kicker) in a `hand`.
 pub fn get_bombs(hand: &[Card]) -> PossibleCombos {
     let hand_copy = hand.to_vec();
 
@@ -102,6 +110,7 @@ pub fn get_bombs(hand: &[Card]) -> PossibleCombos {
     }
 }
 
+/// Get all full houses in a `hand`.
 pub fn get_full_houses(hand: &[Card]) -> PossibleCombos {
     let hand_copy = hand.to_vec();
 
@@ -150,7 +159,13 @@ pub fn get_full_houses(hand: &[Card]) -> PossibleCombos {
     }
 }
 
+/// Get all five card straights in a `hand` using the standard `StraightRules`.
 pub fn get_straights(hand: &[Card]) -> PossibleCombos {
+    get_straights_with(hand, &StraightRules::default())
+}
+
+/// Get all five card straights in a `hand` that are valid under the `straights` rules.
+pub fn get_straights_with(hand: &[Card], straights_rules: &StraightRules) -> PossibleCombos {
     let mut hand_copy = hand.to_vec();
     hand_copy.sort();
 
@@ -210,12 +225,12 @@ pub fn get_straights(hand: &[Card]) -> PossibleCombos {
         let contains_dupe = contig_seq
             .iter()
             .map(|card| card.rank)
-            .any(|rank| duplicate_card_ranks.keys().contains(&rank));
+            .any(|rank| duplicate_card_ranks.contains_key(&rank));
 
         // Store the indices of the contiguous sequence of cards that contain duplicate ranks.
         let duplicate_card_idxs: HashMap<Rank, usize> = duplicate_card_ranks
-            .iter()
-            .filter_map(|(rank, _)| {
+            .keys()
+            .filter_map(|rank| {
                 if let Some((idx, _)) = contig_seq
                     .iter()
                     .enumerate()
@@ -265,6 +280,29 @@ pub fn get_straights(hand: &[Card]) -> PossibleCombos {
             }
         }
     }
+
+    // Add straights that wrap around from 2 to 3.
+    for wrap_ranks in [
+        [Rank::Ace, Rank::Two, Rank::Three, Rank::Four, Rank::Five],
+        [Rank::Two, Rank::Three, Rank::Four, Rank::Five, Rank::Six],
+    ] {
+        let rank_cards = wrap_ranks.map(|rank| {
+            hand_copy
+                .iter()
+                .filter(|card| card.rank == rank)
+                .copied()
+                .collect_vec()
+        });
+        for wrap in rank_cards.iter().multi_cartesian_product() {
+            straights.push(wrap.into_iter().copied().collect_vec());
+        }
+    }
+
+    // Remove straights not allowed by the rules.
+    straights.retain(|straight| straights_rules.rank_straight(straight).is_some());
+    // Keep the same order regardless of how duplicates were swapped in.
+    straights.sort();
+
     if straights.is_empty() {
         None
     } else {
@@ -272,6 +310,7 @@ pub fn get_straights(hand: &[Card]) -> PossibleCombos {
     }
 }
 
+/// Get all five card flushes in a `hand`.
 pub fn get_flushes(hand: &[Card]) -> PossibleCombos {
     let mut hand_copy = hand.to_vec();
     hand_copy.sort();
@@ -297,8 +336,77 @@ pub fn get_flushes(hand: &[Card]) -> PossibleCombos {
     }
 }
 
-/// Generate all possible combos
+/// Get all sequences of at least `min_len` consecutive ranks in a `hand` for Tiến lên.
+///
+/// Each rank in the sequence uses `n_per_rank` cards. 2s are never part of a sequence.
+///
+/// ```
+/// use big2::common::{card::Card, rank::Rank, suit::Suit};
+/// use big2::logic::combo::get_sequences;
+///
+/// let hand = vec![
+///     Card::new(Rank::Three, Suit::Spade),
+///     Card::new(Rank::Four, Suit::Club),
+///     Card::new(Rank::Five, Suit::Heart),
+///     Card::new(Rank::Six, Suit::Heart),
+/// ];
+///
+/// // 3-4-5, 4-5-6 and 3-4-5-6
+/// assert_eq!(get_sequences(&hand, 3, 1).unwrap().len(), 3);
+/// ```
+pub fn get_sequences(hand: &[Card], min_len: usize, n_per_rank: usize) -> PossibleCombos {
+    let mut hand_copy = hand.to_vec();
+    hand_copy.sort();
+
+    // Get the cards for each rank that can be used in a sequence.
+    let rank_choices = hand_copy
+        .iter()
+        .filter(|card| card.rank != Rank::Two)
+        .group_by(|card| card.rank)
+        .into_iter()
+        .filter_map(|(rank, cards)| {
+            let choices = cards.copied().combinations(n_per_rank).collect_vec();
+            (!choices.is_empty()).then_some((rank as usize, choices))
+        })
+        .collect_vec();
+
+    let mut sequences: Vec<Vec<Card>> = vec![];
+    for start in 0..rank_choices.len() {
+        for end in start..rank_choices.len() {
+            // Stop once the ranks are no longer consecutive.
+            if end > start && rank_choices[end].0 - rank_choices[end - 1].0 != 1 {
+                break;
+            }
+            if end + 1 - start < min_len {
+                continue;
+            }
+            for sequence in rank_choices[start..=end]
+                .iter()
+                .map(|(_, choices)| choices)
+                .multi_cartesian_product()
+            {
+                sequences.push(sequence.into_iter().flatten().copied().collect_vec());
+            }
+        }
+    }
+
+    if sequences.is_empty() {
+        None
+    } else {
+        Some(sequences)
+    }
+}
+
+/// Generate all possible combos using the standard `RuleSet`.
 pub fn get_combos(hand: &[Card]) -> Option<HashMap<ComboType, Vec<Vec<Card>>>> {
+    get_combos_with(hand, &RuleSet::default())
+}
+
+/// Generate all possible combos allowed by the `rules`.
+pub fn get_combos_with(
+    hand: &[Card],
+    rules: &RuleSet,
+) -> Option<HashMap<ComboType, Vec<Vec<Card>>>> {
     let mut handles: Vec<JoinHandle<PossibleCombos>> = vec![];
 
     // Define combo names and combo functions.
@@ -308,12 +416,17 @@ pub fn get_combos(hand: &[Card]) -> Option<HashMap<ComboType, Vec<Vec<Card>>>> {
         ComboType::Bomb,
         ComboType::Flush,
     ];
-    let combo_fns: Vec<fn(&[Card]) -> PossibleCombos> =
-        vec![get_straights, get_full_houses, get_bombs, get_flushes];
+    let combo_fns: Vec<fn(&[Card], &StraightRules) -> PossibleCombos> = vec![
+        get_straights_with,
+        |hand, _| get_full_houses(hand),
+        |hand, _| get_bombs(hand),
+        |hand, _| get_flushes(hand),
+    ];
 
     for combo_func in combo_fns {
         let hand_copy = hand.to_vec();
-        let handle = thread::spawn(move || combo_func(&hand_copy));
+        let straights = rules.straights;
+        let handle = thread::spawn(move || combo_func(&hand_copy, &straights));
         handles.push(handle);
     }
 
@@ -333,9 +446,61 @@ pub fn get_combos(hand: &[Card]) -> Option<HashMap<ComboType, Vec<Vec<Card>>>> {
     }
 }
 
+#[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_get_sequences() {
+        let test_seq_file = "test/cards_dupes.json";
+        let cards: Vec<Card> =
+            serde_json::from_reader(&std::fs::File::open(test_seq_file).unwrap()).unwrap();
+
+        // Runs within 9-10-J-Q-K-A. Two jacks and four aces can be swapped in.
+        let sequences = get_sequences(&cards, 3, 1).unwrap();
+        assert_eq!(sequences.len(), 40);
+        assert!(sequences.iter().all(|sequence| sequence.len() >= 3));
+
+        // No consecutive pairs.
+        assert_eq!(get_sequences(&cards, 3, 2), None);
+
+        let pairs = [Rank::Four, Rank::Five, Rank::Six]
+            .into_iter()
+            .flat_map(|rank| [Card::new(rank, Suit::Spade), Card::new(rank, Suit::Heart)])
+            .chain([
+                Card::new(Rank::Two, Suit::Spade),
+                Card::new(Rank::Two, Suit::Heart),
+            ])
+            .collect_vec();
+        assert_eq!(get_sequences(&pairs, 3, 2).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_get_straights_with() {
+        let cards = [
+            Card::new(Rank::Ace, Suit::Heart),
+            Card::new(Rank::Two, Suit::Club),
+            Card::new(Rank::Three, Suit::Club),
+            Card::new(Rank::Four, Suit::Diamond),
+            Card::new(Rank::Five, Suit::Club),
+            Card::new(Rank::Six, Suit::Spade),
+        ];
+        assert_eq!(get_straights(&cards), None);
+
+        let rules = StraightRules {
+            allow_ace_low: true,
+            allow_two_low: true,
+            ..Default::default()
+        };
+        assert_eq!(get_straights_with(&cards, &rules).unwrap().len(), 2);
+
+        let no_twos = StraightRules {
+            allow_twos: false,
+            ..rules
+        };
+        assert_eq!(get_straights_with(&cards, &no_twos), None);
+    }
+
     #[test]
     fn test_get_combo() {
         let test_seq_file = "test/cards_dupes.json";
@@ -0,0 +1,116 @@
+use crate::common::rules::RuleSet;
+use crate::logic::{score::ScoringRules, special::SpecialHandRules};
+
+/// What to do with cards left over after dealing equal hands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Leftover {
+    /// Give the leftover cards to the player holding the 3 of diamonds.
+    #[default]
+    StartingPlayer,
+    /// Set the leftover cards aside, unseen.
+    Discard,
+}
+
+/// Options used to set up a `Game`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameConfig {
+    /// Number of players.
+    pub n_players: usize,
+    /// Number of standard decks combined together.
+    ///
+    /// Defaults to one deck for up to four players and two decks otherwise.
+    pub n_decks: Option<usize>,
+    /// Number of cards dealt to each player.
+    ///
+    /// Defaults to 13 cards for two players or more than four players and an even split otherwise.
+    /// In two-player games, this leaves a hidden dead hand.
+    pub hand_size: Option<usize>,
+    /// What to do with cards that can't be dealt evenly.
+    ///
+    /// Cards not dealt because of a smaller `hand_size` are always set aside.
+    pub leftover: Leftover,
+    /// Rules used to compare cards and hands.
+    pub rules: RuleSet,
+    /// Awards for special hands dealt to a player.
+    pub special_hands: SpecialHandRules,
+    /// Keep playing after the first player goes out until only one player has cards left.
+    pub play_out: bool,
+    /// Seed used to shuffle the deck. Identifies the deal.
+    ///
+    /// The same seed and moves always give the same game. A random seed is picked if not set.
+    pub seed: Option<u64>,
+}
+
+impl GameConfig {
+    /// Number of standard decks used.
+    pub fn n_decks(&self) -> usize {
+        match self.n_decks {
+            Some(n_decks) => n_decks,
+            None if self.n_players > 4 => 2,
+            None => 1,
+        }
+    }
+
+    /// Number of cards dealt to each player from a deck of `n_cards`.
+    pub fn hand_size(&self, n_cards: usize) -> usize {
+        match (self.hand_size, self.n_players) {
+            (Some(hand_size), _) => hand_size,
+            (None, 0) => 0,
+            (None, 2) => 13,
+            (None, n_players) if n_players > 4 => 13,
+            (None, n_players) => n_cards / n_players,
+        }
+    }
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        GameConfig {
+            n_players: 4,
+            n_decks: None,
+            hand_size: None,
+            leftover: Leftover::default(),
+            rules: RuleSet::default(),
+            special_hands: SpecialHandRules::default(),
+            play_out: false,
+            seed: None,
+        }
+    }
+}
+
+/// Who leads the first hand of the next deal in a `Match`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NextLead {
+    /// Holder of the 3 of diamonds leads and must play it.
+    #[default]
+    StartingCard,
+    /// Winner of the previous deal leads with any hand.
+    PreviousWinner,
+}
+
+/// Who can take back moves in a `Match`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum UndoPolicy {
+    /// Any move can be taken back one at a time.
+    #[default]
+    AllSeats,
+    /// Moves are taken back up to the last move of one of these seats.
+    HumanSeats(Vec<usize>),
+    /// Moves can't be taken back, as in rated games.
+    Disabled,
+}
+
+/// Options used to set up a `Match` of multiple deals.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct MatchConfig {
+    /// Options used for each deal.
+    pub game: GameConfig,
+    /// Penalties for cards left at the end of a deal.
+    pub scoring: ScoringRules,
+    /// Penalty score that ends the match. A single deal is played if not set.
+    pub target_score: Option<usize>,
+    /// Who leads after the first deal.
+    pub next_lead: NextLead,
+    /// Who can take back moves during a deal.
+    pub undo: UndoPolicy,
+}
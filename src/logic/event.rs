@@ -0,0 +1,44 @@
+use crate::common::card::Card;
+use serde::{Deserialize, Serialize};
+
+/// A change to the state of a `Game`.
+///
+/// Every `Game` logs its events in order. Replaying the log with [`Game::replay`](crate::logic::game::Game::replay) rebuilds the game.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub enum GameEvent {
+    /// Cards were dealt to each seat. Cards not dealt to anyone are set aside in the `stock`.
+    Deal {
+        hands: Vec<Vec<Card>>,
+        stock: Vec<Card>,
+    },
+    /// The player in `seat` was given a free lead for the first hand.
+    FreeLead { seat: usize },
+    /// The player in `seat` played `cards` on the stack.
+    Play { seat: usize, cards: Vec<Card> },
+    /// The player in `seat` passed.
+    Pass { seat: usize },
+    /// Every other player passed on the hand played by `seat`. The stack is cleared.
+    TrickWon { seat: usize },
+    /// The player in `seat` was placed at `position`, starting from 1.
+    PlayerFinished {
+        seat: usize,
+        position: usize,
+        cards_left: Vec<Card>,
+    },
+    /// Every player was placed. The player in `winner` went out first.
+    GameOver { winner: usize },
+    /// The last `Play` or `Pass` by `seat` was taken back.
+    ///
+    /// Only sent to subscribers. The undone action is removed from the game's own log.
+    Undo { seat: usize },
+}
+
+impl GameEvent {
+    /// Seat that took the action if the event is a `Play` or `Pass`.
+    pub fn actor(&self) -> Option<usize> {
+        match self {
+            GameEvent::Play { seat, .. } | GameEvent::Pass { seat } => Some(*seat),
+            _ => None,
+        }
+    }
+}
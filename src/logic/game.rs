@@ -1,248 +1,1514 @@
 use crate::common::{
-    card::Card, deck::Deck, hand::HandType, player::Player, rank::Rank, stack::CardStack,
-    suit::Suit,
+    card::Card, deck::Deck, error::GameError, hand::Hand, player::Player, rank::Rank,
+    rules::RuleSet, stack::CardStack, suit::Suit,
+};
+use crate::logic::{
+    agent::{Command, HeuristicAgent, Seat, Turn, TERMINAL_KEYS},
+    config::{GameConfig, Leftover, MatchConfig},
+    event::GameEvent,
+    score::Match,
+    special::{SpecialAward, SpecialHand},
 };
-use crate::logic::choice::choose_move;
 use itertools::Itertools;
-use regex::Regex;
-use std::io;
+use rand::{thread_rng, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::sync::mpsc::{channel, Receiver, Sender};
 
+/// Card that must be included in the first hand of the game.
 pub const STARTING_CARD: Card = Card {
     rank: Rank::Three,
     suit: Suit::Diamond,
+    deck: 0,
 };
 
-/// Initialize a game by creating a `Deck` of cards
-///
-fn init(n_players: usize) -> Result<(Deck, Vec<Player>, usize), &'static str> {
-    // Generate deck.
-    let mut players: Vec<Player> = vec![];
-    let new_deck = Deck::new(true);
-    let mut starting_player: usize = 0;
-
-    if let Ok(shuffled_deck) = new_deck {
-        match shuffled_deck.divide(n_players) {
-            Ok(chunks) => {
-                for (i, chunk) in chunks.into_iter().enumerate() {
-                    let deck_chunk = chunk.clone().into_iter().cloned().collect_vec();
-
-                    // Check if starting player.
-                    if deck_chunk.contains(&STARTING_CARD) {
-                        starting_player = i;
-                    }
+/// A move a player can make on their turn.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Action {
+    /// Play a set of cards on top of the stack.
+    Play(Vec<Card>),
+    /// Pass the turn to the next player.
+    Pass,
+}
 
-                    let player = Player {
-                        id: i,
-                        cards: deck_chunk,
-                    };
-                    players.push(player)
-                }
+/// Where a player finished in a game.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Placement {
+    /// Index of the player.
+    pub seat: usize,
+    /// Cards in the player's hand when they were placed. Empty if they went out.
+    pub cards_left: Vec<Card>,
+}
 
-                Ok((shuffled_deck.clone(), players, starting_player))
-            }
-            Err(err_msg) => panic!("{:?}", err_msg),
+/// State of a game of Big 2.
+///
+/// Owns the `Deck`, `Player`s and `CardStack` and enforces turn order.
+/// Drive the game by calling [`Game::apply`] with the current player's `Action`.
+#[derive(Debug)]
+pub struct Game {
+    pub config: GameConfig,
+    pub deck: Deck,
+    pub players: Vec<Player>,
+    /// Cards set aside and not dealt to any player.
+    pub stock: Vec<Card>,
+    pub stack: CardStack,
+    /// Number of actions taken. Starts at 1.
+    pub turn: usize,
+    starting_card: Option<Card>,
+    starting_player: usize,
+    current_player: usize,
+    winner: Option<usize>,
+    finishing_order: Vec<Placement>,
+    special_hands: Vec<(usize, SpecialHand)>,
+    events: Vec<GameEvent>,
+    undone: Vec<GameEvent>,
+    subscribers: Vec<Sender<GameEvent>>,
+}
+
+impl Clone for Game {
+    /// Copy the game state. The copy starts without subscribers so its moves are not sent to them.
+    fn clone(&self) -> Game {
+        Game {
+            config: self.config.clone(),
+            deck: self.deck.clone(),
+            players: self.players.clone(),
+            stock: self.stock.clone(),
+            stack: self.stack.clone(),
+            turn: self.turn,
+            starting_card: self.starting_card,
+            starting_player: self.starting_player,
+            current_player: self.current_player,
+            winner: self.winner,
+            finishing_order: self.finishing_order.clone(),
+            special_hands: self.special_hands.clone(),
+            events: self.events.clone(),
+            undone: self.undone.clone(),
+            subscribers: vec![],
         }
-    } else {
-        panic!("Failed to generate deck.")
     }
 }
 
-/// Get player index based on turn number and number of players.
-fn player_idx(turn_n: usize, n_players: usize) -> usize {
-    (turn_n - 1) % n_players
-}
+impl Game {
+    /// Create a new game by shuffling a `Deck` and dealing it to `n_players`.
+    pub fn new(n_players: usize) -> Result<Game, GameError> {
+        Game::with_config(GameConfig {
+            n_players,
+            ..Default::default()
+        })
+    }
 
-/// Start main game loop.
-pub fn start(n_players: usize, hotseat: bool) {
-    let (_, mut players, mut starting_player) = init(n_players).unwrap();
+    /// Create a new game by shuffling a `Deck` and dealing it based on the `config`.
+    ///
+    /// The deck is shuffled by ChaCha8 seeded with `config.seed`, or a random seed if not set.
+    /// The same seed deals the same cards on every platform.
+    /// Cards are divided evenly between players unless a smaller hand size is set.
+    /// Cards left over from an even split are handled by `config.leftover`.
+    /// Any other undealt cards are set aside as a dead hand in the `stock`.
+    pub fn with_config(config: GameConfig) -> Result<Game, GameError> {
+        let seed = config.seed.unwrap_or_else(|| thread_rng().gen());
+        let mut game = Game::with_rng(config, &mut ChaCha8Rng::seed_from_u64(seed))?;
+        game.config.seed = Some(seed);
+        Ok(game)
+    }
 
-    let mut pile = CardStack::new();
+    /// Create a new game by shuffling a `Deck` with the `rng` and dealing it based on the `config`.
+    ///
+    /// `config.seed` is ignored.
+    pub fn with_rng<R: Rng + ?Sized>(config: GameConfig, rng: &mut R) -> Result<Game, GameError> {
+        if config.n_players == 0 {
+            return Err(GameError::InvalidDeal(
+                "Game must have at least one player.".to_string(),
+            ));
+        }
+        let config = GameConfig {
+            seed: None,
+            ..config
+        };
+        let mut deck = Deck::with_decks(config.n_decks(), false)
+            .map_err(|err| GameError::InvalidDeal(format!("{err:?}")))?;
+        deck.shuffle(rng);
+        let hand_size = config.hand_size(deck.cards.len());
+        let (chunks, leftover) = deck
+            .deal(config.n_players, hand_size)
+            .map_err(|err| GameError::InvalidDeal(format!("{err:?}")))?;
 
-    // https://dhghomon.github.io/easy_rust/Chapter_63.html
-    let user_input_key_msg =
-        "- q : Quit\n- p : Pass\n- h : Print this message.\n- s : Sort hand.\n- r : Restart.\n-c : Player a computer move.\n";
-    let welcome_msg = format!(
-        "\nWelcome to Big 2!\n{user_input_key_msg}\nPlaying against {n_players} players.\n"
-    );
+        let mut players = chunks
+            .into_iter()
+            .enumerate()
+            .map(|(i, chunk)| Player {
+                id: i,
+                cards: chunk.into_iter().cloned().collect_vec(),
+            })
+            .collect_vec();
+        let mut stock = leftover.into_iter().cloned().collect_vec();
 
-    println!("{welcome_msg}");
+        // Give leftover cards to the holder of the lowest card dealt. Usually the 3 of diamonds.
+        let is_even_split = hand_size == deck.cards.len() / config.n_players;
+        if is_even_split && config.leftover == Leftover::StartingPlayer {
+            if let Some(starting_card) = lowest_card(&players, &config.rules) {
+                if let Some(starting_player) = players
+                    .iter_mut()
+                    .find(|player| player.cards.contains(&starting_card))
+                {
+                    starting_player.cards.append(&mut stock);
+                }
+            }
+        }
 
-    let mut user_input = String::new();
-    let card_idx_pattern = Regex::new(r"(\d+,*)+").unwrap();
-    let mut turn_n: usize = 1;
+        Game::deal(config, deck, players, stock)
+    }
 
-    // Main game loop.
-    loop {
-        // First clear the String. Otherwise it will keep adding to it
-        user_input.clear();
+    /// Create a game from already dealt `players`.
+    ///
+    /// The player holding the lowest card, usually the `STARTING_CARD`, goes first.
+    /// A player dealt a special hand worth an instant win wins before the first trick.
+    pub fn from_deal(
+        config: GameConfig,
+        deck: Deck,
+        players: Vec<Player>,
+    ) -> Result<Game, GameError> {
+        Game::deal(config, deck, players, vec![])
+    }
 
-        // Get current player idx to access cards.
-        let curr_player_idx = if turn_n == 1 {
-            starting_player
-        } else {
-            player_idx(turn_n + starting_player, n_players)
+    /// Rebuild a game by replaying its `events` from the deal.
+    ///
+    /// ```
+    /// use big2::{Action, Game};
+    ///
+    /// let mut game = Game::new(4).unwrap();
+    /// let starting_card = game.starting_card().unwrap();
+    /// game.apply(Action::Play(vec![starting_card])).unwrap();
+    ///
+    /// let replayed = Game::replay(game.config.clone(), game.events()).unwrap();
+    /// assert_eq!(replayed.players, game.players);
+    /// assert_eq!(replayed.events(), game.events());
+    /// ```
+    pub fn replay(config: GameConfig, events: &[GameEvent]) -> Result<Game, GameError> {
+        let Some((GameEvent::Deal { hands, stock }, events)) = events.split_first() else {
+            return Err(GameError::InvalidDeal(
+                "Events must start with a deal.".to_string(),
+            ));
         };
+        let mut deck = Deck::with_decks(config.n_decks(), false)
+            .map_err(|err| GameError::InvalidDeal(format!("{err:?}")))?;
+        if let Some(seed) = config.seed {
+            deck.shuffle(&mut ChaCha8Rng::seed_from_u64(seed));
+        }
+        let players = hands
+            .iter()
+            .enumerate()
+            .map(|(i, cards)| Player {
+                id: i,
+                cards: cards.clone(),
+            })
+            .collect_vec();
 
-        if let Some(prev_hand) = pile.stack.last() {
-            // Clear stack as no other player could respond to player's hand.
-            // Allow fresh hand.
-            if prev_hand.player == curr_player_idx {
-                pile.clear();
+        let mut game = Game::deal(config, deck, players, stock.clone())?;
+        for event in events {
+            match event {
+                GameEvent::FreeLead { seat } => {
+                    game.set_leader(*seat)?;
+                }
+                GameEvent::Play { cards, .. } => {
+                    game.apply(Action::Play(cards.clone()))?;
+                }
+                GameEvent::Pass { .. } => {
+                    game.apply(Action::Pass)?;
+                }
+                GameEvent::Undo { .. } => {
+                    game.undo()?;
+                }
+                // Follow from the deal and the actions taken.
+                GameEvent::Deal { .. }
+                | GameEvent::TrickWon { .. }
+                | GameEvent::PlayerFinished { .. }
+                | GameEvent::GameOver { .. } => {}
             }
         }
+        Ok(game)
+    }
 
-        println!("Current Turn: {turn_n}");
-        if let Some(prev_hand) = pile.stack.last() {
-            println!(
-                "Current Hand: {:?} (Player {})",
-                prev_hand.cards,
-                prev_hand.player + 1
-            );
-        } else {
-            println!("Current Hand: None");
-        }
+    /// Create a game from already dealt `players` with undealt cards set aside in the `stock`.
+    fn deal(
+        config: GameConfig,
+        deck: Deck,
+        players: Vec<Player>,
+        stock: Vec<Card>,
+    ) -> Result<Game, GameError> {
+        let Some(starting_card) = lowest_card(&players, &config.rules) else {
+            return Err(GameError::InvalidDeal(
+                "Game must have at least one player with cards.".to_string(),
+            ));
+        };
+        let starting_player = players
+            .iter()
+            .position(|player| player.cards.contains(&starting_card))
+            .unwrap_or(0);
 
-        // Format current mode string if combo.
-        let curr_mode = if pile.kind == HandType::Combo {
-            format!("{:?} ({:?})", pile.kind, pile.combo)
-        } else {
-            format!("{:?}", pile.kind)
+        let special_hands = config.special_hands.check(&players);
+        let winner = special_hands
+            .iter()
+            .find(|(_, special_hand)| {
+                config.special_hands.award(*special_hand) == SpecialAward::InstantWin
+            })
+            .map(|(seat, _)| *seat);
+
+        let mut game = Game {
+            config: GameConfig {
+                n_players: players.len(),
+                ..config.clone()
+            },
+            deck,
+            players,
+            stock,
+            stack: CardStack::with_rules(config.rules),
+            turn: 1,
+            starting_card: Some(starting_card),
+            starting_player,
+            current_player: starting_player,
+            winner,
+            finishing_order: vec![],
+            special_hands,
+            events: vec![],
+            undone: vec![],
+            subscribers: vec![],
         };
+        game.emit(GameEvent::Deal {
+            hands: game
+                .players
+                .iter()
+                .map(|player| player.cards.clone())
+                .collect_vec(),
+            stock: game.stock.clone(),
+        });
+        if let Some(winner) = winner {
+            game.place(winner);
+            game.finish();
+        }
+        Ok(game)
+    }
+
+    /// Seed the deck was shuffled with, if any. Identifies the deal.
+    pub fn seed(&self) -> Option<u64> {
+        self.config.seed
+    }
+
+    /// Card that must be included in the first hand of the game, if any.
+    ///
+    /// This is the `STARTING_CARD` unless it was not dealt.
+    pub fn starting_card(&self) -> Option<Card> {
+        self.starting_card
+    }
+
+    /// Card that must be included in the hand played this turn, if any.
+    ///
+    /// The opening hand must contain the starting card unless the first player was given a free lead.
+    pub fn required_card(&self) -> Option<Card> {
+        self.starting_card.filter(|_| self.turn == 1)
+    }
+
+    /// Let the player in `seat` lead the first hand with any cards.
+    pub fn set_leader(&mut self, seat: usize) -> Result<&Game, GameError> {
+        if self.turn != 1 || seat >= self.players.len() {
+            return Err(GameError::InvalidDeal(format!(
+                "Player {} cannot lead after the game has started.",
+                seat + 1
+            )));
+        }
+        self.starting_card = None;
+        self.starting_player = seat;
+        self.current_player = seat;
+        self.emit(GameEvent::FreeLead { seat });
+        Ok(self)
+    }
+
+    /// Index of the player who started the game.
+    pub fn starting_player(&self) -> usize {
+        self.starting_player
+    }
+
+    /// Index of the player whose turn it is.
+    pub fn current_player(&self) -> usize {
+        self.current_player
+    }
+
+    /// Hand on top of the stack, if any.
+    pub fn top(&self) -> Option<&Hand> {
+        self.stack.stack.last()
+    }
+
+    /// Whether every player has been placed.
+    ///
+    /// This is once a player has emptied their hand or won with a special hand.
+    /// With `config.play_out`, play continues until only one player has cards left.
+    pub fn is_over(&self) -> bool {
+        self.finishing_order.len() == self.players.len()
+    }
+
+    /// Index of the player who won the game, if any.
+    pub fn winner(&self) -> Option<usize> {
+        self.winner
+    }
+
+    /// Players placed so far, from first to last.
+    pub fn finishing_order(&self) -> &[Placement] {
+        &self.finishing_order
+    }
+
+    /// Events of the game so far, starting from the deal.
+    pub fn events(&self) -> &[GameEvent] {
+        &self.events
+    }
 
-        println!("Current Mode: {:?}\n", curr_mode);
-        println!("Your Hand (Player {}):", curr_player_idx + 1);
+    /// Observe events from now on. Events already logged are sent first.
+    pub fn subscribe(&mut self) -> Receiver<GameEvent> {
+        let (sender, receiver) = channel();
+        self.add_subscriber(sender);
+        receiver
+    }
 
-        if let Some(curr_player) = &players.get(curr_player_idx) {
-            for (idx, card) in curr_player.cards.iter().enumerate() {
-                println!("{} - {:?}", idx, card)
+    /// Send events to the `subscriber`, starting with the events already logged.
+    pub fn add_subscriber(&mut self, subscriber: Sender<GameEvent>) {
+        let is_connected = self
+            .events
+            .iter()
+            .all(|event| subscriber.send(event.clone()).is_ok());
+        if is_connected {
+            self.subscribers.push(subscriber);
+        }
+    }
+
+    /// Stop sending events and hand back the subscribers.
+    pub(crate) fn take_subscribers(&mut self) -> Vec<Sender<GameEvent>> {
+        std::mem::take(&mut self.subscribers)
+    }
+
+    /// Special hands with an award dealt to each seat.
+    pub fn special_hands(&self) -> &[(usize, SpecialHand)] {
+        &self.special_hands
+    }
+
+    /// Apply the current player's `action` and move on to the next player.
+    pub fn apply(&mut self, action: Action) -> Result<&Game, GameError> {
+        if let Some(winner) = self.winner.filter(|_| self.is_over()) {
+            return Err(GameError::GameOver(format!(
+                "Game is over. Player {} won.",
+                winner + 1
+            )));
+        }
+
+        match action {
+            Action::Play(cards) => {
+                self.turn_state().check(&cards)?;
+                let player = &mut self.players[self.current_player];
+                self.stack
+                    .add(&cards, player)
+                    .map_err(|err_msg| GameError::InvalidMove(err_msg.to_string()))?;
+                player.remove(&cards)?;
+                let is_out = player.cards.is_empty();
+                self.emit(GameEvent::Play {
+                    seat: self.current_player,
+                    cards,
+                });
+                self.undone.clear();
+
+                if is_out {
+                    self.winner.get_or_insert(self.current_player);
+                    self.place(self.current_player);
+
+                    if !self.config.play_out || self.n_active() <= 1 {
+                        self.finish();
+                        return Ok(self);
+                    }
+                }
+            }
+            Action::Pass => {
+                self.stack
+                    .pass(&self.players[self.current_player])
+                    .map_err(|err_msg| GameError::InvalidMove(err_msg.to_string()))?;
+                self.emit(GameEvent::Pass {
+                    seat: self.current_player,
+                });
+                self.undone.clear();
             }
         }
 
-        // If hotseat, allow user input.
-        if (curr_player_idx == 1) | (hotseat) {
-            // Get the stdin from the user, and put it in user_input.
-            io::stdin().read_line(&mut user_input).unwrap();
-        } else {
-            // If computer player, use computer move.
-            user_input.push('c')
+        self.next_turn();
+        Ok(self)
+    }
+
+    /// Take back the last action, rewinding the players' cards, the stack and the turn order.
+    ///
+    /// The game is rebuilt by replaying its events up to the action. The action can be redone with [`Game::redo`].
+    pub fn undo(&mut self) -> Result<&Game, GameError> {
+        let Some((idx, seat)) = self
+            .events
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(idx, event)| Some((idx, event.actor()?)))
+        else {
+            return Err(GameError::InvalidMove("No moves to undo.".to_string()));
+        };
+
+        let mut game = Game::replay(self.config.clone(), &self.events[..idx])?;
+        game.deck = self.deck.clone();
+        game.undone = std::mem::take(&mut self.undone);
+        game.undone.push(self.events[idx].clone());
+        game.subscribers = self.take_subscribers();
+        game.notify(&GameEvent::Undo { seat });
+
+        *self = game;
+        Ok(self)
+    }
+
+    /// Apply the last action taken back with [`Game::undo`] again.
+    pub fn redo(&mut self) -> Result<&Game, GameError> {
+        let Some(event) = self.undone.pop() else {
+            return Err(GameError::InvalidMove("No moves to redo.".to_string()));
+        };
+        let action = match &event {
+            GameEvent::Play { cards, .. } => Action::Play(cards.clone()),
+            _ => Action::Pass,
+        };
+
+        // Applying an action clears the moves left to redo.
+        let mut undone = std::mem::take(&mut self.undone);
+        if let Err(err) = self.apply(action) {
+            undone.push(event);
+            self.undone = undone;
+            return Err(err);
+        }
+        self.undone = undone;
+        Ok(self)
+    }
+
+    /// Actions taken back with [`Game::undo`] that can be redone, most recent last.
+    pub fn undone(&self) -> &[GameEvent] {
+        &self.undone
+    }
+
+    /// Check that the opening hand contains the required card, if any.
+    pub fn check_opening(&self, cards: &[Card]) -> Result<(), GameError> {
+        self.turn_state().check_opening(cards)
+    }
+
+    /// Check that the current player playing `cards` follows the `last_card` rule.
+    ///
+    /// If the next player holds a single card, a single led must be the player's highest card.
+    /// The opening hand only has to contain the starting card.
+    pub fn check_last_card(&self, cards: &[Card]) -> Result<(), GameError> {
+        self.turn_state().check_last_card(cards)
+    }
+
+    /// State the current player's hand is checked against.
+    fn turn_state(&self) -> TurnState<'_> {
+        let next_seat = self.next_player();
+        TurnState {
+            player: &self.players[self.current_player],
+            top: self.stack.stack.last(),
+            required_card: self.required_card(),
+            next_seat,
+            n_cards_next: self.players[next_seat].cards.len(),
+            rules: &self.stack.rules,
         }
+    }
+
+    /// Index of the player after the current player.
+    fn next_player(&self) -> usize {
+        self.next_player_from(self.current_player)
+    }
 
-        // Match user input.
-        match user_input.trim() {
-            "c" => {
-                let remaining_cards = players
+    /// Index of the first player after `seat` that still has cards.
+    fn next_player_from(&self, seat: usize) -> usize {
+        let n_players = self.players.len();
+        (1..=n_players)
+            .map(|offset| (seat + offset) % n_players)
+            .find(|next_seat| !self.players[*next_seat].cards.is_empty())
+            .unwrap_or((seat + 1) % n_players)
+    }
+
+    /// Number of players that still have cards.
+    fn n_active(&self) -> usize {
+        self.players
+            .iter()
+            .filter(|player| !player.cards.is_empty())
+            .count()
+    }
+
+    /// Add the player in `seat` to the finishing order with the cards they have left.
+    fn place(&mut self, seat: usize) {
+        let cards_left = self.players[seat].cards.clone();
+        self.finishing_order.push(Placement {
+            seat,
+            cards_left: cards_left.clone(),
+        });
+        self.emit(GameEvent::PlayerFinished {
+            seat,
+            position: self.finishing_order.len(),
+            cards_left,
+        });
+    }
+
+    /// Place the remaining players and end the game.
+    fn finish(&mut self) {
+        self.place_remaining();
+        if let Some(winner) = self.winner {
+            self.emit(GameEvent::GameOver { winner });
+        }
+    }
+
+    /// Log the `event` and send it to every subscriber.
+    fn emit(&mut self, event: GameEvent) {
+        self.notify(&event);
+        self.events.push(event);
+    }
+
+    /// Send the `event` to every subscriber still listening.
+    fn notify(&mut self, event: &GameEvent) {
+        self.subscribers
+            .retain(|subscriber| subscriber.send(event.clone()).is_ok());
+    }
+
+    /// Place every player not placed yet. Players with fewer cards left are placed first.
+    fn place_remaining(&mut self) {
+        let remaining = (0..self.players.len())
+            .filter(|seat| {
+                !self
+                    .finishing_order
                     .iter()
-                    .map(|player| player.cards.len())
-                    .collect_vec();
-                let prev_hand = pile.stack.last();
-                if let Some(comp_player) = players.get_mut(curr_player_idx) {
-                    if let Some((hand, player)) = choose_move(
-                        &comp_player.cards,
-                        comp_player,
-                        prev_hand,
-                        curr_player_idx,
-                        &remaining_cards,
-                    ) {
-                        pile.add(&hand.cards, player).unwrap();
-
-                        let new_cards = player
-                            .cards
-                            .iter()
-                            .filter(|card| !hand.cards.contains(&card))
-                            .cloned()
-                            .collect_vec();
-
-                        if new_cards.is_empty() {
-                            println!("Game over.");
-                            break;
-                        }
-                        comp_player.cards = new_cards;
-                    }
+                    .any(|placement| placement.seat == *seat)
+            })
+            .sorted_by_key(|seat| self.players[*seat].cards.len())
+            .collect_vec();
 
-                    turn_n += 1;
-                }
+        for seat in remaining {
+            self.place(seat);
+        }
+    }
+
+    /// Move to the next player that still has cards.
+    ///
+    /// If every other player passed, the trick ends and its winner leads the next trick.
+    /// If the winner of the trick went out, the next player after them leads instead.
+    fn next_turn(&mut self) {
+        self.turn += 1;
+
+        let leader = self.stack.leader();
+        let leader_out = leader.is_some_and(|leader| self.players[leader].cards.is_empty());
+
+        if self
+            .stack
+            .is_finished(self.n_active() + usize::from(leader_out))
+        {
+            if let Some(leader) = leader {
+                self.emit(GameEvent::TrickWon { seat: leader });
+                self.current_player = if leader_out {
+                    self.next_player_from(leader)
+                } else {
+                    leader
+                };
             }
-            "q" => {
+            self.stack.clear();
+        } else {
+            self.current_player = self.next_player();
+        }
+    }
+}
+
+/// What a hand played on a turn is checked against, borrowed from a `Game` or `PlayerView`.
+///
+/// [`Game::apply`] and [`PlayerView::check`](crate::logic::view::PlayerView::check) share these checks so they can't drift apart.
+pub(crate) struct TurnState<'a> {
+    pub player: &'a Player,
+    /// Hand on top of the current trick, if any.
+    pub top: Option<&'a Hand>,
+    pub required_card: Option<Card>,
+    /// Seat of the next player that still has cards.
+    pub next_seat: usize,
+    pub n_cards_next: usize,
+    pub rules: &'a RuleSet,
+}
+
+impl TurnState<'_> {
+    /// Check that the player can play `cards` and return the hand they make.
+    pub fn check(&self, cards: &[Card]) -> Result<Hand, GameError> {
+        self.player.check_holds(cards)?;
+        self.check_opening(cards)?;
+        self.check_last_card(cards)?;
+
+        let hand = Hand::with_rules(cards, self.player, self.rules)
+            .map_err(|err_msg| GameError::InvalidMove(err_msg.to_string()))?;
+        self.rules
+            .check_play(&hand, self.top)
+            .map_err(|err_msg| GameError::InvalidMove(err_msg.to_string()))?;
+        Ok(hand)
+    }
+
+    /// Check that the opening hand contains the required card, if any.
+    pub fn check_opening(&self, cards: &[Card]) -> Result<(), GameError> {
+        match self.required_card {
+            Some(required_card) if !cards.contains(&required_card) => Err(GameError::InvalidMove(
+                format!("First hand must contain the {required_card:?}."),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Check that a single led is the player's highest card when the next player has one card left.
+    pub fn check_last_card(&self, cards: &[Card]) -> Result<(), GameError> {
+        if cards.len() != 1 || self.required_card.is_some() {
+            return Ok(());
+        }
+        let highest_card =
+            self.rules
+                .last_card_single(&self.player.cards, self.top.is_none(), self.n_cards_next);
+
+        match highest_card {
+            Some(highest_card) if cards[0] != highest_card => Err(GameError::InvalidMove(format!(
+                "Player {} has one card left. Must lead your highest single ({:?}).",
+                self.next_seat + 1,
+                highest_card
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Lowest card held by any of the `players`.
+fn lowest_card(players: &[Player], rules: &RuleSet) -> Option<Card> {
+    players
+        .iter()
+        .flat_map(|player| &player.cards)
+        .min_by(|card_1, card_2| rules.cmp_cards(card_1, card_2))
+        .copied()
+}
+
+/// Start main command-line game loop.
+///
+/// Each of the `seats` chooses its moves. Seats left out are played by the computer.
+pub fn start(config: MatchConfig, mut seats: Vec<Seat>) -> Result<(), GameError> {
+    let n_players = config.game.n_players;
+    let mut current = Match::new(config)?;
+    seats.resize_with(n_players, || Seat::Agent(Box::new(HeuristicAgent)));
+
+    // https://dhghomon.github.io/easy_rust/Chapter_63.html
+    let welcome_msg =
+        format!("\nWelcome to Big 2!\n{TERMINAL_KEYS}\nPlaying against {n_players} players.\n");
+
+    println!("{welcome_msg}");
+
+    print_deal(&current.game);
+
+    // Main game loop.
+    loop {
+        // Deal was won with a special hand before the first trick.
+        if let Some(winner) = current.game.winner().filter(|_| current.game.is_over()) {
+            println!("Player {} won the deal.", winner + 1);
+            if finish_deal(&mut current)? {
+                break;
+            }
+            continue;
+        }
+
+        let curr_player_idx = current.game.current_player();
+        let seat = &mut seats[curr_player_idx];
+        let is_human = seat.is_human();
+
+        match take_turn(&mut current, seat) {
+            Ok(Some(Command::Quit)) => {
                 println!("See you later!");
-                // exit_condition = true;
                 break;
             }
-            "p" => {
-                turn_n += 1;
+            Ok(Some(Command::Restart)) => {
+                // Deal new cards rather than the same seeded deal.
+                current.game = Game::with_config(GameConfig {
+                    seed: None,
+                    ..current.config.game.clone()
+                })?;
+                print_deal(&current.game);
+                continue;
             }
-            "h" => {
-                println!("{user_input_key_msg}");
+            Ok(Some(Command::Undo)) => {
+                if let Err(err) = current.undo() {
+                    println!("{err}\n");
+                }
                 continue;
             }
-            "s" => {
-                if let Some(curr_player) = players.get_mut(curr_player_idx) {
-                    let mut sorted_hand = curr_player.cards.clone();
-                    sorted_hand.sort();
-                    curr_player.cards = sorted_hand;
-                    continue;
+            Ok(Some(Command::Redo)) => {
+                if let Err(err) = current.redo() {
+                    println!("{err}\n");
                 }
+                continue;
             }
-            "r" => {
-                let (_, new_players, new_starting_player) = init(n_players).unwrap();
-                let new_pile = CardStack::new();
-                turn_n = 1;
-                starting_player = new_starting_player;
-                players = new_players;
-                pile = new_pile;
+            Ok(None) => {}
+            Err(
+                GameError::InvalidMove(err_msg)
+                | GameError::DuplicateCard(err_msg)
+                | GameError::CardNotHeld(err_msg),
+            ) if is_human => {
+                println!("Played hand is invalid: {err_msg}\n");
+                continue;
             }
-            _ => {
-                // Search user input for pattern.
-                if card_idx_pattern.is_match(user_input.trim()) {
-                    let mut card_idx: Vec<usize> = vec![];
-                    for idx in user_input.trim().split(',') {
-                        if let Ok(parsed_idx) = idx.parse::<usize>() {
-                            card_idx.push(parsed_idx)
-                        } else {
-                            println!("Invalid index. ({idx})\n");
-                            continue;
-                        }
-                    }
+            Err(err) => {
+                // A computer player would repeat the same move, so stop the game.
+                println!("Player {} could not move: {err}\n", curr_player_idx + 1);
+                break;
+            }
+        }
 
-                    if !card_idx.is_empty() {
-                        if let Some(curr_player) = players.get_mut(curr_player_idx) {
-                            let mut new_hand = curr_player.cards.clone();
-
-                            // Iter in reverse to not shift indices.
-                            let mut playing_hand = vec![];
-                            for idx in card_idx.iter().sorted().rev() {
-                                if (0..new_hand.len()).contains(idx) {
-                                    playing_hand.push(new_hand.remove(*idx))
-                                } else {
-                                    println!("Index of ({idx}) is not in hand.")
-                                }
-                            }
-
-                            if turn_n == 1 && !playing_hand.contains(&STARTING_CARD) {
-                                println!("First hand must contain the {:?}\n", &STARTING_CARD);
-                                continue;
-                            };
-
-                            if let Err(err_msg) = pile.add(&playing_hand, curr_player) {
-                                println!("Played hand is invalid: {err_msg}\n");
-                                continue;
-                            } else {
-                                if new_hand.is_empty() {
-                                    println!("You won!");
-                                    break;
-                                }
-                                // Set player hand to new hand.
-                                curr_player.cards = new_hand
-                            }
-                        }
-                        turn_n += 1
-                    }
-                } else {
-                    println!(
-                        "Invalid indices ({}). Doesn't match pattern: \\d,\\d,...\n",
-                        user_input.trim()
-                    );
-                }
+        let game = &current.game;
+        if !game.is_over() {
+            if game.players[curr_player_idx].cards.is_empty() {
+                println!(
+                    "Player {} finished in position {}.\n",
+                    curr_player_idx + 1,
+                    game.finishing_order().len()
+                );
             }
+            continue;
+        }
+
+        if game.winner() == Some(curr_player_idx) && is_human {
+            println!("You won!");
+        } else {
+            println!("Game over.");
+        }
+
+        if finish_deal(&mut current)? {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Ask the current `seat` for its turn and apply its action to the current deal.
+///
+/// Returns the command given instead of an action, if any. Moves by computer players are printed once applied.
+fn take_turn(current: &mut Match, seat: &mut Seat) -> Result<Option<Command>, GameError> {
+    // Only show what the current player is allowed to see.
+    let seat_idx = current.game.current_player();
+    let view = current
+        .game
+        .view(seat_idx)
+        .ok_or_else(|| GameError::InvalidMove(format!("No player in seat {}.", seat_idx + 1)))?;
+
+    let action = match seat.turn(&view) {
+        Turn::Action(action) => action,
+        Turn::Command(command) => return Ok(Some(command)),
+    };
+    current.apply(action.clone())?;
+
+    if !seat.is_human() {
+        match action {
+            Action::Play(cards) => println!("Player {} played {:?}", seat_idx + 1, cards),
+            Action::Pass => println!("Player {} passed.", seat_idx + 1),
         }
     }
+    Ok(None)
+}
+
+/// Report the deal identifier and the special hands dealt to each player.
+fn print_deal(game: &Game) {
+    if let Some(seed) = game.seed() {
+        println!("Deal: {seed} (replay with --seed {seed})\n");
+    }
+    for (seat, special_hand) in game.special_hands() {
+        let award = match game.config.special_hands.award(*special_hand) {
+            SpecialAward::InstantWin => "Instant win".to_string(),
+            SpecialAward::Bonus(bonus) => format!("+{bonus} to each opponent"),
+            SpecialAward::None => continue,
+        };
+        println!("Player {} was dealt {:?}! {award}.", seat + 1, special_hand);
+    }
+}
+
+/// Print the scores of the finished deal and deal the next one.
+///
+/// Returns `true` if the match is over.
+fn finish_deal(current: &mut Match) -> Result<bool, GameError> {
+    if current.game.config.play_out {
+        println!("\nFinishing order:");
+        for (position, placement) in current.game.finishing_order().iter().enumerate() {
+            println!(
+                "{}. Player {} ({} cards left)",
+                position + 1,
+                placement.seat + 1,
+                placement.cards_left.len()
+            )
+        }
+    }
+
+    if let Some(penalties) = current.scoreboard.deals.last() {
+        println!("\nScores:");
+        for (i, (penalty, score)) in penalties.iter().zip(&current.scoreboard.scores).enumerate() {
+            println!("Player {}: +{penalty} ({score})", i + 1)
+        }
+    }
+
+    if let Some(winner) = current.winner() {
+        if current.config.target_score.is_some() {
+            println!("Player {} won the match!", winner + 1);
+        }
+        return Ok(true);
+    }
+    current.next_deal()?;
+    println!("\nNext deal.\n");
+    print_deal(&current.game);
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{take_turn, Action, Game, Placement, STARTING_CARD};
+    use crate::common::{
+        card::Card, deck::Deck, error::GameError, player::Player, rank::Rank, rules::RuleSet,
+        suit::Suit,
+    };
+    use crate::logic::{
+        agent::{HeuristicAgent, ScriptedAgent, Seat},
+        config::{GameConfig, Leftover, MatchConfig},
+        event::GameEvent,
+        score::Match,
+        special::{SpecialAward, SpecialHand, SpecialHandRules},
+    };
+
+    fn get_test_game() -> Game {
+        let players = vec![
+            Player {
+                id: 0,
+                cards: vec![
+                    Card {
+                        rank: Rank::Four,
+                        suit: Suit::Club,
+                        deck: 0,
+                    },
+                    Card {
+                        rank: Rank::Two,
+                        suit: Suit::Spade,
+                        deck: 0,
+                    },
+                ],
+            },
+            Player {
+                id: 1,
+                cards: vec![
+                    STARTING_CARD,
+                    Card {
+                        rank: Rank::Ace,
+                        suit: Suit::Heart,
+                        deck: 0,
+                    },
+                ],
+            },
+        ];
+        Game::from_deal(GameConfig::default(), Deck::new(false).unwrap(), players).unwrap()
+    }
+
+    #[test]
+    fn test_new_game() {
+        let game = Game::new(4).unwrap();
+
+        assert_eq!(game.players.len(), 4);
+        assert!(game.players.iter().all(|player| player.cards.len() == 13));
+        assert!(game.players[game.current_player()]
+            .cards
+            .contains(&STARTING_CARD));
+        assert!(!game.is_over());
+        assert_eq!(game.winner(), None);
+    }
+
+    #[test]
+    fn test_new_game_three_players() {
+        let game = Game::new(3).unwrap();
+        let n_cards = game
+            .players
+            .iter()
+            .map(|player| player.cards.len())
+            .collect::<Vec<usize>>();
+
+        // Leftover card goes to the starting player.
+        assert_eq!(game.players.len(), 3);
+        assert!(game.stock.is_empty());
+        assert_eq!(n_cards.iter().sum::<usize>(), 52);
+        assert_eq!(n_cards[game.starting_player()], 18);
+        assert_eq!(game.starting_card(), Some(STARTING_CARD));
+        assert!(game.players[game.starting_player()]
+            .cards
+            .contains(&STARTING_CARD));
+    }
+
+    #[test]
+    fn test_new_game_three_players_discard() {
+        let game = Game::with_config(GameConfig {
+            n_players: 3,
+            leftover: Leftover::Discard,
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(game.stock.len(), 1);
+        assert!(game.players.iter().all(|player| player.cards.len() == 17));
+
+        // Starting player holds the lowest dealt card.
+        let lowest_card = game
+            .players
+            .iter()
+            .flat_map(|player| player.cards.iter())
+            .min()
+            .unwrap();
+        assert_eq!(game.starting_card(), Some(*lowest_card));
+        assert!(game.players[game.starting_player()]
+            .cards
+            .contains(lowest_card));
+        if game.stock.contains(&STARTING_CARD) {
+            assert_ne!(game.starting_card(), Some(STARTING_CARD));
+        }
+    }
+
+    #[test]
+    fn test_new_game_two_players() {
+        let game = Game::new(2).unwrap();
+
+        // Remaining cards are set aside as a dead hand.
+        assert!(game.players.iter().all(|player| player.cards.len() == 13));
+        assert_eq!(game.stock.len(), 26);
+
+        let lowest_card = game
+            .players
+            .iter()
+            .flat_map(|player| player.cards.iter())
+            .min()
+            .unwrap();
+        assert_eq!(game.starting_card(), Some(*lowest_card));
+        assert!(game.players[game.starting_player()]
+            .cards
+            .contains(lowest_card));
+    }
+
+    #[test]
+    fn test_new_game_hand_size() {
+        let game = Game::with_config(GameConfig {
+            n_players: 3,
+            hand_size: Some(13),
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(game.players.iter().all(|player| player.cards.len() == 13));
+        assert_eq!(game.stock.len(), 13);
+
+        assert!(Game::with_config(GameConfig {
+            n_players: 4,
+            hand_size: Some(14),
+            ..Default::default()
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_new_game_double_deck() {
+        for n_players in 5..=8 {
+            let game = Game::new(n_players).unwrap();
+
+            assert_eq!(game.deck.cards.len(), 104);
+            assert!(game.players.iter().all(|player| player.cards.len() == 13));
+            assert_eq!(game.stock.len(), 104 - 13 * n_players);
+
+            // Copy of the 3 of diamonds from the first deck starts, if dealt.
+            let lowest_card = game
+                .players
+                .iter()
+                .flat_map(|player| player.cards.iter())
+                .min()
+                .unwrap();
+            assert_eq!(game.starting_card(), Some(*lowest_card));
+        }
+    }
+
+    #[test]
+    fn test_new_game_rules() {
+        let game = Game::with_config(GameConfig {
+            rules: RuleSet::pusoy_dos(),
+            ..Default::default()
+        })
+        .unwrap();
+
+        // Clubs are the weakest suit in Pusoy Dos.
+        let three_clubs = Card::new(Rank::Three, Suit::Club);
+        assert_eq!(game.starting_card(), Some(three_clubs));
+        assert!(game.players[game.starting_player()]
+            .cards
+            .contains(&three_clubs));
+        assert_eq!(game.stack.rules, RuleSet::pusoy_dos());
+    }
+
+    #[test]
+    fn test_new_game_instant_win() {
+        // Player 1 holds all four 2s.
+        let deck = Deck::new(false).unwrap();
+        let players = vec![
+            Player {
+                id: 0,
+                cards: deck.cards[13..26].to_vec(),
+            },
+            Player {
+                id: 1,
+                cards: deck
+                    .cards
+                    .iter()
+                    .filter(|card| card.rank == Rank::Two)
+                    .copied()
+                    .chain([STARTING_CARD])
+                    .collect(),
+            },
+        ];
+        let config = GameConfig {
+            special_hands: SpecialHandRules {
+                four_twos: SpecialAward::InstantWin,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let game = Game::from_deal(GameConfig::default(), deck.clone(), players.clone()).unwrap();
+        assert!(!game.is_over());
+        assert!(game.special_hands().is_empty());
+
+        let mut game = Game::from_deal(config, deck, players).unwrap();
+        assert_eq!(game.special_hands(), &[(1, SpecialHand::FourTwos)]);
+        assert!(game.is_over());
+        assert_eq!(game.winner(), Some(1));
+        assert!(game.apply(Action::Play(vec![STARTING_CARD])).is_err());
+    }
+
+    #[test]
+    fn test_new_game_seed() {
+        let config = GameConfig {
+            seed: Some(42),
+            ..Default::default()
+        };
+        let game = Game::with_config(config.clone()).unwrap();
+        let same_game = Game::with_config(config).unwrap();
+        assert_eq!(game.seed(), Some(42));
+        assert_eq!(game.players, same_game.players);
+        assert_eq!(game.deck.cards, same_game.deck.cards);
+
+        // Seeds must keep dealing the same cards across releases and platforms.
+        let card = |rank, suit| Card::new(rank, suit);
+        assert_eq!(
+            game.players[0].cards,
+            vec![
+                card(Rank::Queen, Suit::Heart),
+                card(Rank::Jack, Suit::Club),
+                card(Rank::Seven, Suit::Heart),
+                card(Rank::Two, Suit::Spade),
+                card(Rank::Three, Suit::Diamond),
+                card(Rank::Three, Suit::Club),
+                card(Rank::Nine, Suit::Heart),
+                card(Rank::King, Suit::Spade),
+                card(Rank::Six, Suit::Heart),
+                card(Rank::Five, Suit::Club),
+                card(Rank::Ace, Suit::Club),
+                card(Rank::Seven, Suit::Spade),
+                card(Rank::Jack, Suit::Heart),
+            ]
+        );
+
+        // A random seed is picked and kept to replay the deal.
+        let random_game = Game::new(4).unwrap();
+        let seed = random_game.seed().unwrap();
+        let replayed = Game::replay(random_game.config.clone(), random_game.events()).unwrap();
+        assert_eq!(replayed.deck.cards, random_game.deck.cards);
+        let redealt = Game::with_config(GameConfig {
+            seed: Some(seed),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(redealt.players, random_game.players);
+    }
+
+    #[test]
+    fn test_new_game_no_players() {
+        assert!(Game::new(0).is_err())
+    }
+
+    #[test]
+    fn test_apply_play() {
+        let mut game = get_test_game();
+        assert_eq!(game.current_player(), 1);
+
+        game.apply(Action::Play(vec![STARTING_CARD])).unwrap();
+
+        assert_eq!(game.turn, 2);
+        assert_eq!(game.current_player(), 0);
+        assert!(!game.players[1].cards.contains(&STARTING_CARD));
+        assert_eq!(
+            game.top().map(|hand| hand.cards.clone()),
+            Some(vec![STARTING_CARD])
+        );
+    }
+
+    #[test]
+    fn test_apply_invalid_play() {
+        let mut game = get_test_game();
+        game.apply(Action::Play(vec![STARTING_CARD])).unwrap();
+
+        // Cards of different rank are not a double.
+        let invalid_double = game.players[0].cards.clone();
+        assert!(game.apply(Action::Play(invalid_double)).is_err());
+        assert_eq!(game.current_player(), 0);
+        assert_eq!(game.players[0].cards.len(), 2);
+    }
+
+    #[test]
+    fn test_apply_opening() {
+        let mut game = get_test_game();
+        let ace_hearts = Card::new(Rank::Ace, Suit::Heart);
+        assert_eq!(game.required_card(), Some(STARTING_CARD));
+
+        // First hand must contain the starting card.
+        assert!(game.check_opening(&[ace_hearts]).is_err());
+        assert!(game.apply(Action::Play(vec![ace_hearts])).is_err());
+
+        // Unless the first player is given a free lead.
+        game.set_leader(1).unwrap();
+        assert_eq!(game.required_card(), None);
+        game.apply(Action::Play(vec![ace_hearts])).unwrap();
+    }
+
+    #[test]
+    fn test_apply_cards_not_held() {
+        let mut game = get_test_game();
+        let ace_hearts = Card::new(Rank::Ace, Suit::Heart);
+
+        assert!(matches!(
+            game.apply(Action::Play(vec![STARTING_CARD, STARTING_CARD])),
+            Err(GameError::DuplicateCard(_))
+        ));
+        assert!(matches!(
+            game.apply(Action::Play(vec![
+                STARTING_CARD,
+                Card::new(Rank::Three, Suit::Club)
+            ])),
+            Err(GameError::CardNotHeld(_))
+        ));
+
+        // Nothing is removed from a rejected hand.
+        assert_eq!(game.players[1].cards, vec![STARTING_CARD, ace_hearts]);
+        assert_eq!(game.current_player(), 1);
+    }
+
+    #[test]
+    fn test_apply_pass() {
+        let mut game = get_test_game();
+
+        // Cannot pass on a new hand.
+        assert!(game.apply(Action::Pass).is_err());
+
+        game.apply(Action::Play(vec![STARTING_CARD])).unwrap();
+        game.apply(Action::Pass).unwrap();
+
+        // Stack cleared as no other player responded.
+        assert_eq!(game.current_player(), 1);
+        assert!(game.top().is_none());
+    }
+
+    #[test]
+    fn test_apply_win() {
+        let mut game = get_test_game();
+        let two_spades = Card {
+            rank: Rank::Two,
+            suit: Suit::Spade,
+            deck: 0,
+        };
+
+        game.apply(Action::Play(vec![STARTING_CARD])).unwrap();
+        game.apply(Action::Play(vec![two_spades])).unwrap();
+        game.apply(Action::Pass).unwrap();
+        game.apply(Action::Play(vec![Card {
+            rank: Rank::Four,
+            suit: Suit::Club,
+            deck: 0,
+        }]))
+        .unwrap();
+
+        assert!(game.is_over());
+        assert_eq!(game.winner(), Some(0));
+        assert_eq!(
+            game.finishing_order()
+                .iter()
+                .map(|placement| placement.seat)
+                .collect::<Vec<usize>>(),
+            vec![0, 1]
+        );
+        assert!(game.apply(Action::Pass).is_err());
+    }
+
+    #[test]
+    fn test_events() {
+        let mut game = get_test_game();
+        let events = game.subscribe();
+        let two_spades = Card::new(Rank::Two, Suit::Spade);
+        let four_clubs = Card::new(Rank::Four, Suit::Club);
+
+        game.apply(Action::Play(vec![STARTING_CARD])).unwrap();
+        game.apply(Action::Play(vec![two_spades])).unwrap();
+        game.apply(Action::Pass).unwrap();
+        game.apply(Action::Play(vec![four_clubs])).unwrap();
+
+        assert_eq!(
+            game.events()[1..],
+            [
+                GameEvent::Play {
+                    seat: 1,
+                    cards: vec![STARTING_CARD]
+                },
+                GameEvent::Play {
+                    seat: 0,
+                    cards: vec![two_spades]
+                },
+                GameEvent::Pass { seat: 1 },
+                GameEvent::TrickWon { seat: 0 },
+                GameEvent::Play {
+                    seat: 0,
+                    cards: vec![four_clubs]
+                },
+                GameEvent::PlayerFinished {
+                    seat: 0,
+                    position: 1,
+                    cards_left: vec![]
+                },
+                GameEvent::PlayerFinished {
+                    seat: 1,
+                    position: 2,
+                    cards_left: vec![Card::new(Rank::Ace, Suit::Heart)]
+                },
+                GameEvent::GameOver { winner: 0 },
+            ]
+        );
+        assert_eq!(events.try_iter().collect::<Vec<GameEvent>>(), game.events());
+
+        // Events can be serialized and replayed to rebuild the game.
+        let json = serde_json::to_string(game.events()).unwrap();
+        let logged: Vec<GameEvent> = serde_json::from_str(&json).unwrap();
+        let replayed = Game::replay(game.config.clone(), &logged).unwrap();
+        assert_eq!(replayed.events(), game.events());
+        assert_eq!(replayed.players, game.players);
+        assert_eq!(replayed.finishing_order(), game.finishing_order());
+        assert!(replayed.is_over());
+    }
+
+    #[test]
+    fn test_take_turn_combo() {
+        let full_house = vec![
+            STARTING_CARD,
+            Card::new(Rank::Three, Suit::Club),
+            Card::new(Rank::Five, Suit::Diamond),
+            Card::new(Rank::Five, Suit::Club),
+            Card::new(Rank::Five, Suit::Heart),
+        ];
+        let players = vec![
+            Player {
+                id: 0,
+                // Not in sorted order.
+                cards: vec![
+                    Card::new(Rank::Jack, Suit::Diamond),
+                    Card::new(Rank::Nine, Suit::Diamond),
+                    Card::new(Rank::Jack, Suit::Club),
+                    Card::new(Rank::Nine, Suit::Heart),
+                    Card::new(Rank::Jack, Suit::Heart),
+                    Card::new(Rank::Four, Suit::Club),
+                ],
+            },
+            Player {
+                id: 1,
+                cards: [full_house.clone(), vec![Card::new(Rank::Ace, Suit::Spade)]].concat(),
+            },
+        ];
+        let mut current = Match::new(MatchConfig::default()).unwrap();
+        current.game =
+            Game::from_deal(GameConfig::default(), Deck::new(false).unwrap(), players).unwrap();
+
+        let mut scripted =
+            Seat::Agent(Box::new(ScriptedAgent::new(vec![Action::Play(full_house)])));
+        assert!(take_turn(&mut current, &mut scripted).unwrap().is_none());
+
+        // The computer player's full house is played as chosen.
+        let mut computer = Seat::Agent(Box::new(HeuristicAgent));
+        assert!(take_turn(&mut current, &mut computer).unwrap().is_none());
+        assert!(matches!(
+            current.game.events().last(),
+            Some(GameEvent::Play { seat: 0, cards }) if cards.len() == 5
+        ));
+        assert_eq!(
+            current.game.players[0].cards,
+            vec![Card::new(Rank::Four, Suit::Club)]
+        );
+    }
+
+    #[test]
+    fn test_clone_subscribers() {
+        let mut game = get_test_game();
+        let events = game.subscribe();
+        let deal = events.try_iter().collect::<Vec<GameEvent>>();
+
+        // Moves on a copy are not sent to the original's subscribers.
+        let mut copy = game.clone();
+        copy.apply(Action::Play(vec![STARTING_CARD])).unwrap();
+        assert_eq!(copy.events().len(), deal.len() + 1);
+        assert!(events.try_iter().next().is_none());
+    }
+
+    #[test]
+    fn test_undo_redo() {
+        let mut game = get_test_game();
+        let events = game.subscribe();
+        let two_spades = Card::new(Rank::Two, Suit::Spade);
+        assert!(game.undo().is_err());
+
+        game.apply(Action::Play(vec![STARTING_CARD])).unwrap();
+        game.apply(Action::Play(vec![two_spades])).unwrap();
+        game.apply(Action::Pass).unwrap();
+        let after_trick = game.clone();
+
+        // Take back the pass and the 2 of spades.
+        game.undo().unwrap();
+        game.undo().unwrap();
+        assert_eq!(game.current_player(), 0);
+        assert_eq!(game.turn, 2);
+        assert!(game.players[0].cards.contains(&two_spades));
+        assert_eq!(game.top().unwrap().cards, vec![STARTING_CARD]);
+        assert_eq!(game.undone().len(), 2);
+
+        game.redo().unwrap();
+        game.redo().unwrap();
+        assert!(game.redo().is_err());
+        assert_eq!(game.players, after_trick.players);
+        assert_eq!(game.events(), after_trick.events());
+        assert_eq!(game.current_player(), 0);
+        assert!(game.top().is_none());
+
+        // A new move can't be redone past.
+        game.undo().unwrap();
+        game.undo().unwrap();
+        game.apply(Action::Pass).unwrap();
+        assert!(game.undone().is_empty());
+
+        // Subscribers are told about undos and can replay the stream.
+        let received = events.try_iter().collect::<Vec<GameEvent>>();
+        assert!(received.contains(&GameEvent::Undo { seat: 1 }));
+        let replayed = Game::replay(game.config.clone(), &received).unwrap();
+        assert_eq!(replayed.events(), game.events());
+    }
+
+    #[test]
+    fn test_apply_play_out() {
+        let mut game = get_test_game();
+        game.config.play_out = true;
+        let ace_hearts = Card::new(Rank::Ace, Suit::Heart);
+        game.players[1]
+            .cards
+            .push(Card::new(Rank::Six, Suit::Heart));
+        game.players.push(Player {
+            id: 2,
+            cards: vec![
+                Card::new(Rank::Five, Suit::Diamond),
+                Card::new(Rank::King, Suit::Club),
+            ],
+        });
+
+        game.apply(Action::Play(vec![STARTING_CARD])).unwrap();
+        game.apply(Action::Play(vec![Card::new(Rank::Five, Suit::Diamond)]))
+            .unwrap();
+        game.apply(Action::Play(vec![Card::new(Rank::Two, Suit::Spade)]))
+            .unwrap();
+        game.apply(Action::Pass).unwrap();
+        game.apply(Action::Pass).unwrap();
+        game.apply(Action::Play(vec![Card::new(Rank::Four, Suit::Club)]))
+            .unwrap();
+
+        // Player 1 went out but the game continues.
+        assert!(!game.is_over());
+        assert_eq!(game.winner(), Some(0));
+        assert_eq!(game.current_player(), 1);
+
+        // Player 1 is skipped. Player 2 leads after everyone passes on their last card.
+        game.apply(Action::Pass).unwrap();
+        game.apply(Action::Pass).unwrap();
+        assert_eq!(game.current_player(), 1);
+        assert!(game.top().is_none());
+
+        game.apply(Action::Play(vec![Card::new(Rank::Six, Suit::Heart)]))
+            .unwrap();
+        assert_eq!(game.current_player(), 2);
+        game.apply(Action::Play(vec![Card::new(Rank::King, Suit::Club)]))
+            .unwrap();
+
+        assert!(game.is_over());
+        assert_eq!(
+            game.finishing_order(),
+            &[
+                Placement {
+                    seat: 0,
+                    cards_left: vec![]
+                },
+                Placement {
+                    seat: 2,
+                    cards_left: vec![]
+                },
+                Placement {
+                    seat: 1,
+                    cards_left: vec![ace_hearts]
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_trick_won() {
+        let mut game = get_test_game();
+        game.players.push(Player {
+            id: 2,
+            cards: vec![Card {
+                rank: Rank::King,
+                suit: Suit::Club,
+                deck: 0,
+            }],
+        });
+
+        game.apply(Action::Play(vec![STARTING_CARD])).unwrap();
+        game.apply(Action::Pass).unwrap();
+
+        // Trick continues until every other player passes.
+        assert_eq!(game.current_player(), 0);
+        assert_eq!(game.stack.passes, vec![2]);
+
+        game.apply(Action::Pass).unwrap();
+
+        // Winner of the trick leads on a fresh stack.
+        assert_eq!(game.current_player(), 1);
+        assert!(game.top().is_none());
+        assert!(game.stack.passes.is_empty());
+    }
+
+    #[test]
+    fn test_apply_last_card() {
+        let mut game = get_test_game();
+        game.stack.rules.last_card = true;
+        let four_clubs = Card::new(Rank::Four, Suit::Club);
+        let six_clubs = Card::new(Rank::Six, Suit::Club);
+        game.players[0].cards.push(six_clubs);
+
+        // Responding to a single is not restricted.
+        game.apply(Action::Play(vec![STARTING_CARD])).unwrap();
+        game.apply(Action::Play(vec![Card::new(Rank::Two, Suit::Spade)]))
+            .unwrap();
+        game.apply(Action::Pass).unwrap();
+
+        // Player 2 has one card left. Player 1 must lead their highest single.
+        assert!(game.check_last_card(&[four_clubs]).is_err());
+        assert!(game.check_last_card(&[six_clubs]).is_ok());
+        assert!(game.apply(Action::Play(vec![four_clubs])).is_err());
+        game.apply(Action::Play(vec![six_clubs])).unwrap();
+        assert_eq!(game.current_player(), 1);
+    }
 }
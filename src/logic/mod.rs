@@ -1,3 +1,12 @@
+//! Combo enumeration, computer move selection and the game loop.
+
+pub mod agent;
 pub mod choice;
 pub mod combo;
+pub mod config;
+pub mod event;
 pub mod game;
+pub mod moves;
+pub mod score;
+pub mod special;
+pub mod view;
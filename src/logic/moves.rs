@@ -0,0 +1,276 @@
+use crate::common::{
+    card::Card,
+    hand::MIN_SEQUENCE_LEN,
+    rules::{RuleSet, Variant},
+};
+use crate::logic::{
+    combo::{get_combos_with, get_dupes, get_sequences},
+    game::{Action, Game},
+    view::PlayerView,
+};
+use itertools::Itertools;
+
+/// Get every hand the player in `seat` holds that can be played on top of the `Game`'s stack.
+///
+/// Pass is included when the player is not leading a new hand.
+/// Returns no moves if it is not the player's turn or the game is over.
+///
+/// ```
+/// use big2::{logic::moves::legal_moves, Action, Game};
+///
+/// let game = Game::new(4).unwrap();
+/// let moves = legal_moves(&game, game.current_player());
+///
+/// // First hand must contain the 3 of diamonds.
+/// assert!(moves.iter().all(|action| match action {
+///     Action::Play(cards) => cards.contains(&big2::STARTING_CARD),
+///     Action::Pass => false,
+/// }));
+/// ```
+pub fn legal_moves(state: &Game, seat: usize) -> Vec<Action> {
+    state
+        .view(seat)
+        .map_or_else(Vec::new, |view| legal_moves_in(&view))
+}
+
+/// Get every hand the viewing player holds that can be played on top of the current trick.
+///
+/// Same as [`legal_moves`] but only uses what the player is allowed to see.
+pub fn legal_moves_in(view: &PlayerView) -> Vec<Action> {
+    if view.is_over() || !view.is_turn() {
+        return vec![];
+    }
+    let mut moves = get_hands(&view.cards, &view.rules)
+        .into_iter()
+        .filter(|cards| view.check(cards).is_ok())
+        .map(Action::Play)
+        .collect_vec();
+
+    if !view.trick.is_empty() {
+        moves.push(Action::Pass)
+    }
+
+    moves
+}
+
+/// Get all singles, doubles, triples, quads and either five-card combos or Tiến lên sequences in `cards` without duplicates.
+///
+/// Hands not allowed by the rules are rejected when checked against the stack.
+pub(crate) fn get_hands(cards: &[Card], rules: &RuleSet) -> Vec<Vec<Card>> {
+    let singles = cards.iter().map(|card| vec![*card]).collect_vec();
+    let dupes = (2..=4)
+        .flat_map(|size| get_dupes(cards, size).unwrap_or_default())
+        .collect_vec();
+    let combos = match rules.variant {
+        Variant::BigTwo => get_combos_with(cards, rules)
+            .map(|combos| combos.into_values().flatten().collect_vec())
+            .unwrap_or_default(),
+        Variant::TienLen => [1, 2]
+            .into_iter()
+            .flat_map(|n_per_rank| {
+                get_sequences(cards, MIN_SEQUENCE_LEN, n_per_rank).unwrap_or_default()
+            })
+            .collect_vec(),
+    };
+
+    singles
+        .into_iter()
+        .chain(dupes)
+        .chain(combos)
+        .map(|hand| hand.into_iter().sorted().collect_vec())
+        .unique()
+        .collect_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::legal_moves;
+    use crate::common::{
+        card::Card, deck::Deck, player::Player, rank::Rank, rules::RuleSet, suit::Suit,
+    };
+    use crate::logic::{
+        config::GameConfig,
+        game::{Action, Game, STARTING_CARD},
+    };
+
+    fn get_test_game() -> Game {
+        let test_seq_file = "test/cards_dupes.json";
+        let cards: Vec<Card> =
+            serde_json::from_reader(&std::fs::File::open(test_seq_file).unwrap()).unwrap();
+        let players = vec![
+            Player {
+                id: 0,
+                cards: vec![
+                    STARTING_CARD,
+                    Card {
+                        rank: Rank::Four,
+                        suit: Suit::Club,
+                        deck: 0,
+                    },
+                ],
+            },
+            Player { id: 1, cards },
+        ];
+        Game::from_deal(GameConfig::default(), Deck::new(false).unwrap(), players).unwrap()
+    }
+
+    #[test]
+    fn test_legal_moves_opening() {
+        let game = get_test_game();
+        let moves = legal_moves(&game, 0);
+
+        // Only the 3 of diamonds can be played. No passing on a new hand.
+        assert_eq!(moves, vec![Action::Play(vec![STARTING_CARD])]);
+    }
+
+    #[test]
+    fn test_legal_moves_not_turn() {
+        let game = get_test_game();
+        assert!(legal_moves(&game, 1).is_empty());
+        assert!(legal_moves(&game, 5).is_empty());
+    }
+
+    #[test]
+    fn test_legal_moves_respond_single() {
+        let mut game = get_test_game();
+        game.apply(Action::Play(vec![STARTING_CARD])).unwrap();
+
+        let moves = legal_moves(&game, 1);
+        let n_cards = game.players[1].cards.len();
+
+        // Every single beats the 3 of diamonds and pass is allowed.
+        assert_eq!(moves.len(), n_cards + 1);
+        assert!(moves.contains(&Action::Pass));
+        assert!(moves.iter().all(|action| match action {
+            Action::Play(cards) => cards.len() == 1,
+            Action::Pass => true,
+        }));
+    }
+
+    #[test]
+    fn test_legal_moves_game_over() {
+        let mut game = get_test_game();
+        game.apply(Action::Play(vec![STARTING_CARD])).unwrap();
+        game.apply(Action::Pass).unwrap();
+        game.apply(Action::Play(vec![Card {
+            rank: Rank::Four,
+            suit: Suit::Club,
+            deck: 0,
+        }]))
+        .unwrap();
+
+        assert!(game.is_over());
+        assert!(legal_moves(&game, 1).is_empty());
+    }
+
+    #[test]
+    fn test_legal_moves_lead() {
+        let mut game = get_test_game();
+        game.apply(Action::Play(vec![STARTING_CARD])).unwrap();
+        game.apply(Action::Pass).unwrap();
+
+        // Player 0 leads a new hand so cannot pass.
+        let moves = legal_moves(&game, 0);
+        assert_eq!(
+            moves,
+            vec![Action::Play(vec![Card {
+                rank: Rank::Four,
+                suit: Suit::Club,
+                deck: 0,
+            }])]
+        );
+    }
+
+    #[test]
+    fn test_legal_moves_all_kinds() {
+        let mut game = get_test_game();
+        game.apply(Action::Play(vec![STARTING_CARD])).unwrap();
+        game.stack.clear();
+
+        // Player 1 can play singles, doubles and combos on a fresh stack.
+        let moves = legal_moves(&game, 1);
+        let n_moves = |n_cards: usize| {
+            moves
+                .iter()
+                .filter(|action| matches!(action, Action::Play(cards) if cards.len() == n_cards))
+                .count()
+        };
+        assert!(!moves.contains(&Action::Pass));
+        assert_eq!(n_moves(1), game.players[1].cards.len());
+        assert!(n_moves(2) > 0);
+        assert!(n_moves(5) > 0);
+    }
+
+    #[test]
+    fn test_legal_moves_triples() {
+        let mut game = get_test_game();
+        game.apply(Action::Play(vec![STARTING_CARD])).unwrap();
+        game.stack.clear();
+        let n_moves = |game: &Game| {
+            legal_moves(game, 1)
+                .iter()
+                .filter(|action| matches!(action, Action::Play(cards) if cards.len() == 3))
+                .count()
+        };
+
+        assert_eq!(n_moves(&game), 0);
+
+        game.stack.rules.allow_triples = true;
+        assert!(n_moves(&game) > 0);
+    }
+
+    #[test]
+    fn test_legal_moves_tien_len() {
+        let sequence = [Rank::Four, Rank::Five, Rank::Six]
+            .into_iter()
+            .map(|rank| Card::new(rank, Suit::Spade))
+            .collect::<Vec<Card>>();
+        let players = vec![
+            Player {
+                id: 0,
+                cards: [sequence.clone(), vec![Card::new(Rank::Three, Suit::Spade)]].concat(),
+            },
+            Player {
+                id: 1,
+                cards: vec![Card::new(Rank::Seven, Suit::Spade)],
+            },
+        ];
+        let config = GameConfig {
+            rules: RuleSet::tien_len(),
+            ..Default::default()
+        };
+        let mut game = Game::from_deal(config, Deck::new(false).unwrap(), players).unwrap();
+
+        // 3 of spades is the lowest card. Can open with it or a sequence starting from it.
+        let moves = legal_moves(&game, 0);
+        assert_eq!(moves.len(), 3);
+        assert!(moves.contains(&Action::Play(
+            [vec![Card::new(Rank::Three, Suit::Spade)], sequence.clone()].concat()
+        )));
+
+        game.apply(Action::Play(vec![Card::new(Rank::Three, Suit::Spade)]))
+            .unwrap();
+        game.apply(Action::Pass).unwrap();
+
+        // No five-card combos. Any sequence of 3 or more can be led.
+        let moves = legal_moves(&game, 0);
+        assert!(moves.contains(&Action::Play(sequence)));
+    }
+
+    #[test]
+    fn test_legal_moves_last_card() {
+        let mut game = get_test_game();
+        game.stack.rules.last_card = true;
+        game.players[1].cards = vec![Card::new(Rank::Ace, Suit::Heart)];
+        game.apply(Action::Play(vec![STARTING_CARD])).unwrap();
+        game.apply(Action::Pass).unwrap();
+
+        // Next player has one card left. Only the highest single can be led.
+        let highest_card = Card::new(Rank::Two, Suit::Spade);
+        game.players[0].cards.push(highest_card);
+        assert_eq!(
+            legal_moves(&game, 0),
+            vec![Action::Play(vec![highest_card])]
+        );
+    }
+}
@@ -0,0 +1,504 @@
+use crate::common::{card::Card, error::GameError, rank::Rank};
+use crate::logic::{
+    config::{GameConfig, MatchConfig, NextLead, UndoPolicy},
+    event::GameEvent,
+    game::{Action, Game},
+    special::SpecialAward,
+};
+use itertools::Itertools;
+
+/// Penalties for cards left in a player's hand at the end of a deal.
+///
+/// Reference: https://www.pagat.com/climbing/bigtwo.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScoringRules {
+    /// Number of cards left at which the penalty is doubled.
+    pub double_at: usize,
+    /// Number of cards left at which the penalty is tripled.
+    pub triple_at: usize,
+    /// Multiplier applied to the penalty for each 2 left in hand. Set to 1 to disable.
+    pub twos_multiplier: usize,
+}
+
+impl Default for ScoringRules {
+    fn default() -> Self {
+        ScoringRules {
+            double_at: 10,
+            triple_at: 13,
+            twos_multiplier: 2,
+        }
+    }
+}
+
+impl ScoringRules {
+    /// Calculate the penalty for `cards` left in a player's hand.
+    ///
+    /// ```
+    /// use big2::{logic::score::ScoringRules, Card, Rank, Suit};
+    ///
+    /// let rules = ScoringRules::default();
+    /// let cards = vec![Card::new(Rank::Two, Suit::Spade), Card::new(Rank::Four, Suit::Club)];
+    ///
+    /// // One point per card, doubled for the 2.
+    /// assert_eq!(rules.penalty(&cards), 4);
+    /// ```
+    pub fn penalty(&self, cards: &[Card]) -> usize {
+        let n_cards = cards.len();
+        let n_twos = cards.iter().filter(|card| card.rank == Rank::Two).count() as u32;
+
+        let multiplier = if n_cards >= self.triple_at {
+            3
+        } else if n_cards >= self.double_at {
+            2
+        } else {
+            1
+        };
+
+        n_cards * multiplier * self.twos_multiplier.pow(n_twos)
+    }
+
+    /// Calculate the penalty of each player in a `game`.
+    ///
+    /// The winner takes no penalty. Bonuses for special hands are paid by every other player.
+    pub fn penalties(&self, game: &Game) -> Vec<usize> {
+        let bonuses = game
+            .special_hands()
+            .iter()
+            .filter_map(|(seat, special_hand)| {
+                match game.config.special_hands.award(*special_hand) {
+                    SpecialAward::Bonus(bonus) => Some((*seat, bonus)),
+                    _ => None,
+                }
+            })
+            .collect_vec();
+
+        game.players
+            .iter()
+            .enumerate()
+            .map(|(i, player)| {
+                let penalty = if game.winner() == Some(i) {
+                    0
+                } else {
+                    self.penalty(&player.cards)
+                };
+                let bonus_paid: usize = bonuses
+                    .iter()
+                    .filter(|(seat, _)| *seat != i)
+                    .map(|(_, bonus)| bonus)
+                    .sum();
+                penalty + bonus_paid
+            })
+            .collect_vec()
+    }
+}
+
+/// Running penalty scores across deals.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Scoreboard {
+    /// Total penalty of each player.
+    pub scores: Vec<usize>,
+    /// Penalties of each player for every deal played.
+    pub deals: Vec<Vec<usize>>,
+}
+
+impl Scoreboard {
+    /// Create an empty scoreboard for `n_players`.
+    pub fn new(n_players: usize) -> Scoreboard {
+        Scoreboard {
+            scores: vec![0; n_players],
+            deals: vec![],
+        }
+    }
+
+    /// Add the `penalties` of a deal to the running scores.
+    pub fn record(&mut self, penalties: Vec<usize>) -> &Scoreboard {
+        for (score, penalty) in self.scores.iter_mut().zip(&penalties) {
+            *score += penalty
+        }
+        self.deals.push(penalties);
+        self
+    }
+
+    /// Index of the player with the lowest score. Ties go to the earliest seat.
+    pub fn leader(&self) -> Option<usize> {
+        self.scores
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, score)| **score)
+            .map(|(i, _)| i)
+    }
+}
+
+/// A series of deals scored until a player reaches the target score.
+#[derive(Debug, Clone)]
+pub struct Match {
+    pub config: MatchConfig,
+    pub scoreboard: Scoreboard,
+    /// The current deal.
+    pub game: Game,
+}
+
+impl Match {
+    /// Create a new match and deal the first game.
+    ///
+    /// Deals are shuffled with consecutive seeds starting from `config.game.seed`, or a random seed if not set.
+    pub fn new(mut config: MatchConfig) -> Result<Match, GameError> {
+        let game = Game::with_config(config.game.clone())?;
+        config.game.seed = game.seed();
+        let mut new_match = Match {
+            scoreboard: Scoreboard::new(config.game.n_players),
+            config,
+            game,
+        };
+        new_match.record_if_over();
+        Ok(new_match)
+    }
+
+    /// Record penalties once the deal is over.
+    fn record_if_over(&mut self) {
+        if self.game.is_over() {
+            let penalties = self.config.scoring.penalties(&self.game);
+            self.scoreboard.record(penalties);
+        }
+    }
+
+    /// Apply the current player's `action` to the current deal.
+    ///
+    /// Penalties are recorded once the deal is over.
+    pub fn apply(&mut self, action: Action) -> Result<&Match, GameError> {
+        self.game.apply(action)?;
+        self.record_if_over();
+        Ok(self)
+    }
+
+    /// Take back moves in the current deal as allowed by `config.undo`.
+    ///
+    /// With `UndoPolicy::HumanSeats`, every move after the last move of a human seat is taken back along with it.
+    pub fn undo(&mut self) -> Result<&Game, GameError> {
+        if self.game.is_over() {
+            return Err(GameError::GameOver(
+                "Cannot undo moves once the deal is over.".to_string(),
+            ));
+        }
+
+        match &self.config.undo {
+            UndoPolicy::Disabled => {
+                return Err(GameError::InvalidMove(
+                    "Undo is disabled for this match.".to_string(),
+                ));
+            }
+            UndoPolicy::AllSeats => {
+                self.game.undo()?;
+            }
+            UndoPolicy::HumanSeats(seats) => {
+                let has_human_move = self
+                    .game
+                    .events()
+                    .iter()
+                    .filter_map(GameEvent::actor)
+                    .any(|seat| seats.contains(&seat));
+                if !has_human_move {
+                    return Err(GameError::InvalidMove("No moves to undo.".to_string()));
+                }
+
+                while let Some(seat) = self.game.events().iter().rev().find_map(GameEvent::actor) {
+                    self.game.undo()?;
+                    if seats.contains(&seat) {
+                        break;
+                    }
+                }
+            }
+        }
+        Ok(&self.game)
+    }
+
+    /// Apply moves taken back with [`Match::undo`] again.
+    ///
+    /// With `UndoPolicy::HumanSeats`, moves of other seats are redone up to the next move of a human seat.
+    pub fn redo(&mut self) -> Result<&Game, GameError> {
+        match &self.config.undo {
+            UndoPolicy::Disabled => {
+                return Err(GameError::InvalidMove(
+                    "Undo is disabled for this match.".to_string(),
+                ));
+            }
+            UndoPolicy::AllSeats => {
+                self.game.redo()?;
+            }
+            UndoPolicy::HumanSeats(seats) => {
+                self.game.redo()?;
+                while let Some(seat) = self.game.undone().last().and_then(GameEvent::actor) {
+                    if seats.contains(&seat) {
+                        break;
+                    }
+                    self.game.redo()?;
+                }
+            }
+        }
+        self.record_if_over();
+        Ok(&self.game)
+    }
+
+    /// Deal the next game of the match.
+    pub fn next_deal(&mut self) -> Result<&Game, GameError> {
+        if !self.game.is_over() {
+            return Err(GameError::InvalidDeal(
+                "Current deal must be finished first.".to_string(),
+            ));
+        }
+        if self.is_over() {
+            return Err(GameError::GameOver("Match is over.".to_string()));
+        }
+
+        let n_deals = self.scoreboard.deals.len() as u64;
+        let mut game = Game::with_config(GameConfig {
+            seed: self.config.game.seed.map(|seed| seed.wrapping_add(n_deals)),
+            ..self.config.game.clone()
+        })?;
+        if let (NextLead::PreviousWinner, Some(winner)) =
+            (self.config.next_lead, self.game.winner())
+        {
+            game.set_leader(winner)?;
+        }
+        // Keep observing the match from the next deal.
+        for subscriber in self.game.take_subscribers() {
+            game.add_subscriber(subscriber);
+        }
+        self.game = game;
+        self.record_if_over();
+        Ok(&self.game)
+    }
+
+    /// Whether a player has reached the target score, or the single deal is finished.
+    pub fn is_over(&self) -> bool {
+        if !self.game.is_over() {
+            return false;
+        }
+        match self.config.target_score {
+            Some(target_score) => self
+                .scoreboard
+                .scores
+                .iter()
+                .any(|score| *score >= target_score),
+            None => true,
+        }
+    }
+
+    /// Index of the player with the lowest score once the match is over.
+    pub fn winner(&self) -> Option<usize> {
+        if self.is_over() {
+            self.scoreboard.leader()
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Match, Scoreboard, ScoringRules};
+    use crate::common::{card::Card, deck::Deck, player::Player, rank::Rank, suit::Suit};
+    use crate::logic::{
+        config::{GameConfig, MatchConfig, NextLead, UndoPolicy},
+        event::GameEvent,
+        game::{Action, Game, STARTING_CARD},
+        special::{SpecialAward, SpecialHandRules},
+    };
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn test_penalty() {
+        let rules = ScoringRules::default();
+        let cards = Deck::new(false).unwrap().cards;
+        let no_twos = cards
+            .iter()
+            .filter(|card| card.rank != Rank::Two)
+            .copied()
+            .collect::<Vec<Card>>();
+
+        assert_eq!(rules.penalty(&[]), 0);
+        assert_eq!(rules.penalty(&no_twos[..9]), 9);
+        // Doubled at 10 cards.
+        assert_eq!(rules.penalty(&no_twos[..10]), 20);
+
+        // Tripled at 13 cards. Doubled again for each 2.
+        let all_twos = Suit::iter()
+            .map(|suit| Card::new(Rank::Two, suit))
+            .collect::<Vec<Card>>();
+        let mut thirteen_cards = all_twos.clone();
+        thirteen_cards.extend(&no_twos[..9]);
+        assert_eq!(rules.penalty(&thirteen_cards), 13 * 3 * 16);
+
+        let no_twos_rules = ScoringRules {
+            twos_multiplier: 1,
+            ..Default::default()
+        };
+        assert_eq!(no_twos_rules.penalty(&all_twos), 4);
+    }
+
+    #[test]
+    fn test_scoreboard() {
+        let mut scoreboard = Scoreboard::new(3);
+        scoreboard.record(vec![0, 5, 2]);
+        scoreboard.record(vec![3, 0, 1]);
+
+        assert_eq!(scoreboard.scores, vec![3, 5, 3]);
+        assert_eq!(scoreboard.deals.len(), 2);
+        assert_eq!(scoreboard.leader(), Some(0));
+    }
+
+    fn get_test_match(config: MatchConfig) -> Match {
+        let players = vec![
+            Player {
+                id: 0,
+                cards: vec![STARTING_CARD],
+            },
+            Player {
+                id: 1,
+                cards: vec![
+                    Card::new(Rank::Two, Suit::Spade),
+                    Card::new(Rank::Four, Suit::Club),
+                ],
+            },
+        ];
+        let mut test_match = Match::new(config).unwrap();
+        test_match.game =
+            Game::from_deal(GameConfig::default(), Deck::new(false).unwrap(), players).unwrap();
+        test_match
+    }
+
+    #[test]
+    fn test_match() {
+        let config = MatchConfig {
+            game: GameConfig {
+                n_players: 2,
+                ..Default::default()
+            },
+            target_score: Some(4),
+            next_lead: NextLead::PreviousWinner,
+            ..Default::default()
+        };
+        let mut test_match = get_test_match(config);
+
+        // Cannot deal again until the current deal is over.
+        assert!(test_match.next_deal().is_err());
+
+        test_match.apply(Action::Play(vec![STARTING_CARD])).unwrap();
+        assert!(test_match.game.is_over());
+        assert_eq!(test_match.scoreboard.scores, vec![0, 4]);
+        assert!(test_match.is_over());
+        assert_eq!(test_match.winner(), Some(0));
+        assert!(test_match.next_deal().is_err());
+    }
+
+    #[test]
+    fn test_match_next_deal() {
+        let config = MatchConfig {
+            game: GameConfig {
+                n_players: 2,
+                ..Default::default()
+            },
+            target_score: Some(100),
+            next_lead: NextLead::PreviousWinner,
+            ..Default::default()
+        };
+        let mut test_match = get_test_match(config);
+        let events = test_match.game.subscribe();
+        test_match.apply(Action::Play(vec![STARTING_CARD])).unwrap();
+        assert!(!test_match.is_over());
+        assert_eq!(test_match.winner(), None);
+
+        // Previous winner leads with any hand.
+        let seed = test_match.config.game.seed.unwrap();
+        let game = test_match.next_deal().unwrap();
+        assert_eq!(game.current_player(), 0);
+        assert_eq!(game.starting_card(), None);
+        assert!(game.players.iter().all(|player| player.cards.len() == 13));
+
+        // Next deal is shuffled with the next seed.
+        assert_eq!(game.seed(), Some(seed + 1));
+
+        // Subscribers keep observing the next deal.
+        let received = events.try_iter().collect::<Vec<GameEvent>>();
+        assert_eq!(&received[received.len() - 2..], game.events());
+        assert_eq!(game.events()[1], GameEvent::FreeLead { seat: 0 });
+    }
+
+    #[test]
+    fn test_match_undo() {
+        let config = MatchConfig {
+            game: GameConfig {
+                n_players: 2,
+                ..Default::default()
+            },
+            undo: UndoPolicy::HumanSeats(vec![1]),
+            ..Default::default()
+        };
+        let mut test_match = get_test_match(config);
+        let players = vec![
+            Player {
+                id: 0,
+                cards: vec![STARTING_CARD, Card::new(Rank::Five, Suit::Club)],
+            },
+            test_match.game.players[1].clone(),
+        ];
+        test_match.game =
+            Game::from_deal(GameConfig::default(), Deck::new(false).unwrap(), players).unwrap();
+
+        // Computer moves can't be taken back on their own.
+        test_match.apply(Action::Play(vec![STARTING_CARD])).unwrap();
+        assert!(test_match.undo().is_err());
+
+        test_match
+            .apply(Action::Play(vec![Card::new(Rank::Two, Suit::Spade)]))
+            .unwrap();
+        test_match.apply(Action::Pass).unwrap();
+
+        // Human move is taken back with the computer moves after it.
+        let game = test_match.undo().unwrap();
+        assert_eq!(game.current_player(), 1);
+        assert_eq!(game.undone().len(), 2);
+
+        let game = test_match.redo().unwrap();
+        assert!(game.undone().is_empty());
+        assert_eq!(game.current_player(), 1);
+        assert!(game.top().is_none());
+
+        test_match.config.undo = UndoPolicy::Disabled;
+        assert!(test_match.undo().is_err());
+        assert!(test_match.redo().is_err());
+    }
+
+    #[test]
+    fn test_special_hand_bonus() {
+        let cards = Deck::new(false).unwrap().cards;
+        let players = vec![
+            Player {
+                id: 0,
+                cards: vec![STARTING_CARD],
+            },
+            Player {
+                id: 1,
+                // All four 2s.
+                cards: cards
+                    .iter()
+                    .filter(|card| card.rank == Rank::Two)
+                    .copied()
+                    .collect(),
+            },
+        ];
+        let config = GameConfig {
+            special_hands: SpecialHandRules {
+                four_twos: SpecialAward::Bonus(5),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut game = Game::from_deal(config, Deck::new(false).unwrap(), players).unwrap();
+        game.apply(Action::Play(vec![STARTING_CARD])).unwrap();
+
+        // Player 0 wins but pays the bonus. Player 1 is penalised for four 2s.
+        let rules = ScoringRules::default();
+        assert_eq!(rules.penalties(&game), vec![5, 4 * 16]);
+    }
+}
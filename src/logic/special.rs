@@ -0,0 +1,150 @@
+use crate::common::{card::Card, player::Player, rank::Rank};
+use itertools::Itertools;
+use strum::IntoEnumIterator;
+
+/// Hands checked for right after the deal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SpecialHand {
+    /// One card of every rank from 3 to 2.
+    Dragon,
+    /// All four 2s.
+    FourTwos,
+    /// Six pairs. Four of a kind counts as two pairs.
+    SixPairs,
+}
+
+impl SpecialHand {
+    /// Special hands from strongest to weakest.
+    pub const ALL: [SpecialHand; 3] = [
+        SpecialHand::Dragon,
+        SpecialHand::FourTwos,
+        SpecialHand::SixPairs,
+    ];
+
+    /// Check if `cards` contain the special hand.
+    pub fn is_held(&self, cards: &[Card]) -> bool {
+        let rank_cnts = cards.iter().map(|card| card.rank).counts();
+        match self {
+            SpecialHand::Dragon => Rank::iter().all(|rank| rank_cnts.contains_key(&rank)),
+            SpecialHand::FourTwos => rank_cnts.get(&Rank::Two).copied().unwrap_or(0) >= 4,
+            SpecialHand::SixPairs => rank_cnts.values().map(|cnt| cnt / 2).sum::<usize>() >= 6,
+        }
+    }
+}
+
+/// What a player gets for being dealt a special hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SpecialAward {
+    /// The special hand is ignored.
+    #[default]
+    None,
+    /// The player wins the deal before the first trick.
+    InstantWin,
+    /// Every other player takes this many penalty points at the end of the deal.
+    Bonus(usize),
+}
+
+/// Award for each special hand. All special hands are ignored by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SpecialHandRules {
+    pub dragon: SpecialAward,
+    pub four_twos: SpecialAward,
+    pub six_pairs: SpecialAward,
+}
+
+impl SpecialHandRules {
+    /// Every special hand wins the deal instantly.
+    pub fn instant_wins() -> SpecialHandRules {
+        SpecialHandRules {
+            dragon: SpecialAward::InstantWin,
+            four_twos: SpecialAward::InstantWin,
+            six_pairs: SpecialAward::InstantWin,
+        }
+    }
+
+    /// Award for a `special_hand`.
+    pub fn award(&self, special_hand: SpecialHand) -> SpecialAward {
+        match special_hand {
+            SpecialHand::Dragon => self.dragon,
+            SpecialHand::FourTwos => self.four_twos,
+            SpecialHand::SixPairs => self.six_pairs,
+        }
+    }
+
+    /// Find the special hands with an award dealt to `players`.
+    ///
+    /// Ordered from strongest to weakest special hand, then by seat.
+    pub fn check(&self, players: &[Player]) -> Vec<(usize, SpecialHand)> {
+        SpecialHand::ALL
+            .into_iter()
+            .filter(|special_hand| self.award(*special_hand) != SpecialAward::None)
+            .flat_map(|special_hand| {
+                players
+                    .iter()
+                    .enumerate()
+                    .filter(move |(_, player)| special_hand.is_held(&player.cards))
+                    .map(move |(seat, _)| (seat, special_hand))
+            })
+            .collect_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SpecialAward, SpecialHand, SpecialHandRules};
+    use crate::common::{card::Card, deck::Deck, player::Player, rank::Rank, suit::Suit};
+    use itertools::Itertools;
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn test_is_held() {
+        let dragon = Rank::iter()
+            .map(|rank| Card::new(rank, Suit::Heart))
+            .collect_vec();
+        let four_twos = Suit::iter()
+            .map(|suit| Card::new(Rank::Two, suit))
+            .collect_vec();
+        let six_pairs = [Rank::Three, Rank::Five, Rank::Seven, Rank::Nine]
+            .into_iter()
+            .flat_map(|rank| [Card::new(rank, Suit::Club), Card::new(rank, Suit::Spade)])
+            .chain(four_twos.clone())
+            .collect_vec();
+
+        assert!(SpecialHand::Dragon.is_held(&dragon));
+        assert!(!SpecialHand::Dragon.is_held(&dragon[1..]));
+        assert!(SpecialHand::FourTwos.is_held(&four_twos));
+        assert!(!SpecialHand::FourTwos.is_held(&four_twos[1..]));
+        assert!(SpecialHand::SixPairs.is_held(&six_pairs));
+        assert!(!SpecialHand::SixPairs.is_held(&six_pairs[2..]));
+    }
+
+    #[test]
+    fn test_check() {
+        let cards = Deck::new(false).unwrap().cards;
+        let twos = cards
+            .iter()
+            .filter(|card| card.rank == Rank::Two)
+            .copied()
+            .collect_vec();
+        let players = vec![
+            Player {
+                id: 0,
+                cards: cards[1..13].to_vec(),
+            },
+            Player { id: 1, cards: twos },
+        ];
+
+        // Ignored by default.
+        assert!(SpecialHandRules::default().check(&players).is_empty());
+
+        let rules = SpecialHandRules {
+            four_twos: SpecialAward::Bonus(10),
+            ..Default::default()
+        };
+        assert_eq!(rules.check(&players), vec![(1, SpecialHand::FourTwos)]);
+        assert_eq!(
+            SpecialHandRules::instant_wins().award(SpecialHand::Dragon),
+            SpecialAward::InstantWin
+        );
+    }
+}
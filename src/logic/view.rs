@@ -0,0 +1,170 @@
+use crate::common::{card::Card, error::GameError, hand::Hand, player::Player, rules::RuleSet};
+use crate::logic::{
+    event::GameEvent,
+    game::{Game, TurnState},
+};
+use itertools::Itertools;
+
+/// What the player in a `seat` is allowed to see of a `Game`.
+///
+/// Other players' cards are hidden. Only how many cards they hold is shown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlayerView {
+    /// Seat of the player viewing the game.
+    pub seat: usize,
+    /// Cards held by the player.
+    pub cards: Vec<Card>,
+    /// Number of cards held by each seat.
+    pub n_cards_left: Vec<usize>,
+    /// Hands played in the current trick, oldest first.
+    pub trick: Vec<Hand>,
+    /// Seats that passed since the last hand was played.
+    pub passes: Vec<usize>,
+    /// Seat whose turn it is.
+    pub current_player: usize,
+    /// Card that must be included in the hand played this turn, if any.
+    pub required_card: Option<Card>,
+    /// Number of actions taken. Starts at 1.
+    pub turn: usize,
+    /// Rules used to compare hands.
+    pub rules: RuleSet,
+    /// Seats placed so far, from first to last.
+    pub finishing_order: Vec<usize>,
+    /// Events seen by every player so far. The deal is left out.
+    pub history: Vec<GameEvent>,
+}
+
+impl PlayerView {
+    /// Hand on top of the current trick, if any.
+    pub fn top(&self) -> Option<&Hand> {
+        self.trick.last()
+    }
+
+    /// Whether it is the viewing player's turn.
+    pub fn is_turn(&self) -> bool {
+        self.seat == self.current_player
+    }
+
+    /// Whether every player was placed.
+    pub fn is_over(&self) -> bool {
+        self.finishing_order.len() == self.n_cards_left.len()
+    }
+
+    /// Seat of the first player after the viewing player that still has cards.
+    pub fn next_seat(&self) -> usize {
+        let n_players = self.n_cards_left.len();
+        (1..=n_players)
+            .map(|offset| (self.seat + offset) % n_players)
+            .find(|next_seat| self.n_cards_left[*next_seat] > 0)
+            .unwrap_or((self.seat + 1) % n_players)
+    }
+
+    /// Number of cards held by the next seat that still has cards.
+    pub fn n_cards_next(&self) -> usize {
+        self.n_cards_left[self.next_seat()]
+    }
+
+    /// Check that the viewing player can play `cards` on the current trick.
+    ///
+    /// These are the checks [`Game::apply`] makes, so [`legal_moves_in`](crate::logic::moves::legal_moves_in) lists exactly the hands it accepts.
+    pub fn check(&self, cards: &[Card]) -> Result<Hand, GameError> {
+        let player = self.player();
+        TurnState {
+            player: &player,
+            top: self.top(),
+            required_card: self.required_card,
+            next_seat: self.next_seat(),
+            n_cards_next: self.n_cards_next(),
+            rules: &self.rules,
+        }
+        .check(cards)
+    }
+
+    /// The viewing player with their cards.
+    pub fn player(&self) -> Player {
+        Player {
+            id: self.seat,
+            cards: self.cards.clone(),
+        }
+    }
+}
+
+impl Game {
+    /// View of the game for the player in `seat`. Returns `None` if there is no such seat.
+    pub fn view(&self, seat: usize) -> Option<PlayerView> {
+        let player = self.players.get(seat)?;
+
+        Some(PlayerView {
+            seat,
+            cards: player.cards.clone(),
+            n_cards_left: self
+                .players
+                .iter()
+                .map(|player| player.cards.len())
+                .collect_vec(),
+            trick: self.stack.stack.clone(),
+            passes: self.stack.passes.clone(),
+            current_player: self.current_player(),
+            required_card: self.required_card(),
+            turn: self.turn,
+            rules: self.stack.rules.clone(),
+            finishing_order: self
+                .finishing_order()
+                .iter()
+                .map(|placement| placement.seat)
+                .collect_vec(),
+            history: self
+                .events()
+                .iter()
+                .filter(|event| !matches!(event, GameEvent::Deal { .. }))
+                .cloned()
+                .collect_vec(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{card::Card, deck::Deck, player::Player, rank::Rank, suit::Suit};
+    use crate::logic::{
+        config::GameConfig,
+        event::GameEvent,
+        game::{Action, Game, STARTING_CARD},
+    };
+
+    #[test]
+    fn test_view() {
+        let players = vec![
+            Player {
+                id: 0,
+                cards: vec![STARTING_CARD, Card::new(Rank::Four, Suit::Club)],
+            },
+            Player {
+                id: 1,
+                cards: vec![Card::new(Rank::Ace, Suit::Heart)],
+            },
+        ];
+        let mut game =
+            Game::from_deal(GameConfig::default(), Deck::new(false).unwrap(), players).unwrap();
+        game.apply(Action::Play(vec![STARTING_CARD])).unwrap();
+
+        let view = game.view(1).unwrap();
+        assert_eq!(view.cards, vec![Card::new(Rank::Ace, Suit::Heart)]);
+        assert_eq!(view.n_cards_left, vec![1, 1]);
+        assert_eq!(view.top().unwrap().cards, vec![STARTING_CARD]);
+        assert!(view.is_turn());
+        assert_eq!(
+            view.history,
+            vec![GameEvent::Play {
+                seat: 0,
+                cards: vec![STARTING_CARD]
+            }]
+        );
+
+        // Other players' cards are never shown.
+        let view = game.view(0).unwrap();
+        assert!(!view.is_turn());
+        assert!(!format!("{view:?}").contains(&format!("{:?}", Card::new(Rank::Ace, Suit::Heart))));
+        assert!(game.view(2).is_none());
+    }
+}
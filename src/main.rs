@@ -1,13 +1,106 @@
-use clap::Parser;
+use big2::logic::{
+    agent::{HeuristicAgent, RandomAgent, Seat, TerminalAgent},
+    config::{GameConfig, Leftover, MatchConfig, NextLead, UndoPolicy},
+    game,
+};
+use big2::{FlushRanking, RuleSet, SpecialHandRules};
+use clap::{CommandFactory, ErrorKind, Parser};
+use rand::{thread_rng, Rng};
 
-use crate::args::GameArgs;
-use crate::logic::game;
+use crate::args::{FlushRankingArg, GameArgs, SeatArg};
 
 fn main() {
     let args = GameArgs::parse();
-    game::start(args.players, args.hotseat)
+    if args.players > 4 && args.decks == Some(1) {
+        GameArgs::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "More than four players need at least two decks.",
+            )
+            .exit();
+    }
+    let flush_ranking = match args.flush_ranking {
+        FlushRankingArg::TopCard => FlushRanking::TopCard,
+        FlushRankingArg::SuitFirst => FlushRanking::SuitFirst,
+        FlushRankingArg::CardByCard => FlushRanking::CardByCard,
+    };
+    let seats = (0..args.players)
+        .map(|seat| match args.seats.get(seat) {
+            Some(seat_arg) => *seat_arg,
+            None if args.hotseat || (args.seats.is_empty() && seat == 1) => SeatArg::Human,
+            None => SeatArg::Computer,
+        })
+        .collect::<Vec<SeatArg>>();
+    let human_seats = (0..args.players)
+        .filter(|seat| seats[*seat] == SeatArg::Human)
+        .collect::<Vec<usize>>();
+    let agents = seats
+        .iter()
+        .enumerate()
+        .map(|(seat, seat_arg)| match seat_arg {
+            SeatArg::Human => Seat::Terminal(TerminalAgent::default()),
+            SeatArg::Computer => Seat::Agent(Box::new(HeuristicAgent)),
+            // Random players replay the same moves with the same seed.
+            SeatArg::Random => Seat::Agent(Box::new(RandomAgent::new(
+                args.seed
+                    .unwrap_or_else(|| thread_rng().gen())
+                    .wrapping_add(seat as u64),
+            ))),
+        })
+        .collect::<Vec<Seat>>();
+    let config = MatchConfig {
+        game: GameConfig {
+            n_players: args.players,
+            n_decks: args.decks,
+            leftover: if args.discard_leftover {
+                Leftover::Discard
+            } else {
+                Leftover::StartingPlayer
+            },
+            rules: if args.tien_len {
+                RuleSet {
+                    last_card: args.last_card,
+                    ..RuleSet::tien_len()
+                }
+            } else {
+                RuleSet {
+                    allow_triples: args.triples,
+                    allow_quads: args.quads,
+                    bomb_interrupts: args.bomb_interrupts,
+                    flush_ranking,
+                    last_card: args.last_card,
+                    ..Default::default()
+                }
+            },
+            special_hands: if args.instant_wins {
+                SpecialHandRules::instant_wins()
+            } else {
+                SpecialHandRules::default()
+            },
+            play_out: args.play_out,
+            seed: args.seed,
+            ..Default::default()
+        },
+        target_score: args.target_score,
+        next_lead: if args.winner_leads {
+            NextLead::PreviousWinner
+        } else {
+            NextLead::StartingCard
+        },
+        // Against computer players, only the moves of people are taken back.
+        undo: if args.rated {
+            UndoPolicy::Disabled
+        } else if human_seats.len() == args.players {
+            UndoPolicy::AllSeats
+        } else {
+            UndoPolicy::HumanSeats(human_seats)
+        },
+        ..Default::default()
+    };
+    if let Err(err) = game::start(config, agents) {
+        eprintln!("{err}");
+        std::process::exit(1);
+    }
 }
 
 mod args;
-mod common;
-mod logic;